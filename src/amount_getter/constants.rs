@@ -0,0 +1,23 @@
+// https://github.com/1inch/limit-order-protocol/tree/v3.0.1/contracts/helpers
+pub const GET_RANGE_TAKER_AMOUNT: &str =
+    "getRangeTakerAmount(uint256,uint256,uint256,uint256,uint256)";
+pub const GET_RANGE_MAKER_AMOUNT: &str =
+    "getRangeMakerAmount(uint256,uint256,uint256,uint256,uint256)";
+pub const DUTCH_AUCTION_GET_TAKING_AMOUNT: &str =
+    "getTakingAmount(uint256,uint256,uint256,uint256,uint256)";
+pub const DUTCH_AUCTION_GET_MAKING_AMOUNT: &str =
+    "getMakingAmount(uint256,uint256,uint256,uint256,uint256)";
+pub const SINGLE_PRICE: &str = "singlePrice(address,uint256,uint256)";
+pub const DOUBLE_PRICE: &str = "doublePrice(address,address,int256,uint256,uint256)";
+
+// range prices are set for 1e18 of maker asset
+pub const RANGE_PRICE_DENOMINATOR: u64 = 1_000_000_000_000_000_000;
+
+// chainlink spread is scaled by 1e9, i.e. 101% = 1.01e9
+pub const SPREAD_DENOMINATOR: u64 = 1_000_000_000;
+pub const INVERSE_FLAG_BIT: usize = 255;
+
+// startTimeEndTime = start time (128 bits) | end time (128 bits)
+pub const DUTCH_AUCTION_START_TIME_SHIFT: usize = 128;
+
+pub const TARGET_LENGTH: usize = 20;
@@ -0,0 +1,738 @@
+pub mod constants;
+pub mod types;
+
+use ethers::{
+    abi::{decode, encode, ParamType, Token},
+    types::{Address, Bytes, I256, U256, U512},
+    utils::id,
+};
+
+use crate::utils::Maskn;
+
+use constants::*;
+use types::{AmountGetter, DutchAuction, GetterContext};
+
+impl AmountGetter {
+    pub fn build(&self) -> Bytes {
+        let (calculator, signature, tokens) = match self {
+            AmountGetter::RangeTakingAmount {
+                calculator,
+                price_start,
+                price_end,
+                order_making_amount,
+            } => (
+                calculator,
+                GET_RANGE_TAKER_AMOUNT,
+                uints(&[*price_start, *price_end, *order_making_amount]),
+            ),
+            AmountGetter::RangeMakingAmount {
+                calculator,
+                price_start,
+                price_end,
+                order_making_amount,
+            } => (
+                calculator,
+                GET_RANGE_MAKER_AMOUNT,
+                uints(&[*price_start, *price_end, *order_making_amount]),
+            ),
+            AmountGetter::DutchAuctionTakingAmount {
+                calculator,
+                auction,
+            } => (
+                calculator,
+                DUTCH_AUCTION_GET_TAKING_AMOUNT,
+                auction.to_tokens(),
+            ),
+            AmountGetter::DutchAuctionMakingAmount {
+                calculator,
+                auction,
+            } => (
+                calculator,
+                DUTCH_AUCTION_GET_MAKING_AMOUNT,
+                auction.to_tokens(),
+            ),
+            AmountGetter::ChainlinkSinglePrice {
+                calculator,
+                oracle,
+                inverse,
+                spread,
+            } => {
+                let mut inverse_and_spread = *spread;
+
+                if *inverse {
+                    inverse_and_spread |= U256::one() << INVERSE_FLAG_BIT;
+                }
+
+                (
+                    calculator,
+                    SINGLE_PRICE,
+                    vec![Token::Address(*oracle), Token::Uint(inverse_and_spread)],
+                )
+            }
+            AmountGetter::ChainlinkDoublePrice {
+                calculator,
+                oracle1,
+                oracle2,
+                decimals_scale,
+                spread,
+            } => (
+                calculator,
+                DOUBLE_PRICE,
+                vec![
+                    Token::Address(*oracle1),
+                    Token::Address(*oracle2),
+                    Token::Int(I256::from(*decimals_scale).into_raw()),
+                    Token::Uint(*spread),
+                ],
+            ),
+        };
+
+        [calculator.as_bytes(), &id(signature), &encode(&tokens)]
+            .concat()
+            .into()
+    }
+
+    pub fn decode(getter: &Bytes) -> Result<Self, String> {
+        if getter.len() < TARGET_LENGTH + 4 {
+            return Err("Getter is too short".to_string());
+        }
+
+        let calculator = Address::from_slice(&getter[0..TARGET_LENGTH]);
+        let selector = &getter[TARGET_LENGTH..TARGET_LENGTH + 4];
+        let args = &getter[TARGET_LENGTH + 4..];
+
+        let signature = [
+            GET_RANGE_TAKER_AMOUNT,
+            GET_RANGE_MAKER_AMOUNT,
+            DUTCH_AUCTION_GET_TAKING_AMOUNT,
+            DUTCH_AUCTION_GET_MAKING_AMOUNT,
+            SINGLE_PRICE,
+            DOUBLE_PRICE,
+        ]
+        .into_iter()
+        .find(|signature| id(signature) == selector)
+        .ok_or_else(|| format!("Unknown getter selector 0x{}", hex::encode(selector)))?;
+
+        let types = prefix_param_types(signature);
+
+        if args.len() != types.len() * 32 {
+            return Err(format!(
+                "Expected {} arguments of getter, got {} bytes",
+                types.len(),
+                args.len()
+            ));
+        }
+
+        let mut tokens = decode(&types, args).map_err(|e| e.to_string())?.into_iter();
+        let mut next = || tokens.next().unwrap();
+
+        let getter = match signature {
+            GET_RANGE_TAKER_AMOUNT => AmountGetter::RangeTakingAmount {
+                calculator,
+                price_start: next().into_uint().unwrap(),
+                price_end: next().into_uint().unwrap(),
+                order_making_amount: next().into_uint().unwrap(),
+            },
+            GET_RANGE_MAKER_AMOUNT => AmountGetter::RangeMakingAmount {
+                calculator,
+                price_start: next().into_uint().unwrap(),
+                price_end: next().into_uint().unwrap(),
+                order_making_amount: next().into_uint().unwrap(),
+            },
+            DUTCH_AUCTION_GET_TAKING_AMOUNT | DUTCH_AUCTION_GET_MAKING_AMOUNT => {
+                let start_time_end_time = next().into_uint().unwrap();
+                let auction = DutchAuction {
+                    start_time: auction_time(
+                        start_time_end_time >> DUTCH_AUCTION_START_TIME_SHIFT,
+                    )?,
+                    end_time: auction_time(
+                        start_time_end_time.maskn(DUTCH_AUCTION_START_TIME_SHIFT),
+                    )?,
+                    taking_amount_start: next().into_uint().unwrap(),
+                    taking_amount_end: next().into_uint().unwrap(),
+                    making_amount: next().into_uint().unwrap(),
+                };
+
+                if signature == DUTCH_AUCTION_GET_TAKING_AMOUNT {
+                    AmountGetter::DutchAuctionTakingAmount {
+                        calculator,
+                        auction,
+                    }
+                } else {
+                    AmountGetter::DutchAuctionMakingAmount {
+                        calculator,
+                        auction,
+                    }
+                }
+            }
+            SINGLE_PRICE => {
+                let oracle = next().into_address().unwrap();
+                let inverse_and_spread = next().into_uint().unwrap();
+
+                AmountGetter::ChainlinkSinglePrice {
+                    calculator,
+                    oracle,
+                    inverse: inverse_and_spread.bit(INVERSE_FLAG_BIT),
+                    spread: inverse_and_spread.maskn(INVERSE_FLAG_BIT),
+                }
+            }
+            _ => {
+                let oracle1 = next().into_address().unwrap();
+                let oracle2 = next().into_address().unwrap();
+                let decimals_scale = I256::from_raw(next().into_int().unwrap());
+
+                AmountGetter::ChainlinkDoublePrice {
+                    calculator,
+                    oracle1,
+                    oracle2,
+                    decimals_scale: i64::try_from(decimals_scale).map_err(|_| {
+                        format!("Decimals scale {} is out of range", decimals_scale)
+                    })?,
+                    spread: next().into_uint().unwrap(),
+                }
+            }
+        };
+
+        Ok(getter)
+    }
+
+    // reproduces the value returned by the getter for requested amount,
+    // arithmetic errors are returned where the contract would revert
+    pub fn evaluate(&self, amount: &U256, context: &GetterContext) -> Result<U256, String> {
+        match self {
+            AmountGetter::RangeTakingAmount {
+                price_start,
+                price_end,
+                order_making_amount,
+                ..
+            } => {
+                let filled = already_filled(order_making_amount, context)?;
+                let price_delta = price_delta(price_start, price_end)?;
+                let average_price = add(
+                    div(
+                        mul(price_delta, add(mul(filled, 2.into())?, *amount)?)?,
+                        *order_making_amount,
+                    )?,
+                    mul(*price_start, 2.into())?,
+                )?;
+
+                div(
+                    mul(average_price, *amount)?,
+                    U256::from(RANGE_PRICE_DENOMINATOR) * 2,
+                )
+            }
+            AmountGetter::RangeMakingAmount {
+                price_start,
+                price_end,
+                order_making_amount,
+                ..
+            } => {
+                let filled = already_filled(order_making_amount, context)?;
+                let price_delta = price_delta(price_start, price_end)?;
+
+                if price_delta.is_zero() {
+                    return div(mul(*amount, RANGE_PRICE_DENOMINATOR.into())?, *price_start);
+                }
+
+                // root of d * m^2 + 2 * (d * filled + price_start * M) * m - 2e18 * M * amount = 0
+                let d = U512::from(price_delta);
+                // products of two 256 bit values fit, their sums and squares may not
+                let b = (d * U512::from(filled))
+                    .checked_add(U512::from(*price_start) * U512::from(*order_making_amount))
+                    .ok_or_else(overflow)?;
+                let ac = (U512::from(RANGE_PRICE_DENOMINATOR) * U512::from(2) * d)
+                    .checked_mul(U512::from(*order_making_amount) * U512::from(*amount))
+                    .ok_or_else(overflow)?;
+                let discriminant = b
+                    .checked_mul(b)
+                    .and_then(|b2| b2.checked_add(ac))
+                    .ok_or_else(overflow)?;
+
+                U256::try_from((discriminant.integer_sqrt() - b) / d)
+                    .map_err(|_| "Making amount overflow".to_string())
+            }
+            AmountGetter::DutchAuctionTakingAmount { auction, .. } => div(
+                mul(*amount, auction.taking_amount(context.time)?)?,
+                auction.making_amount,
+            ),
+            AmountGetter::DutchAuctionMakingAmount { auction, .. } => div(
+                mul(auction.making_amount, *amount)?,
+                auction.taking_amount(context.time)?,
+            ),
+            AmountGetter::ChainlinkSinglePrice {
+                oracle,
+                inverse,
+                spread,
+                ..
+            } => {
+                let (answer, decimals) = oracle_answer(oracle, context)?;
+                let spread_amount = mul(*amount, *spread)?;
+
+                let result = if *inverse {
+                    div(mul(spread_amount, decimals)?, answer)?
+                } else {
+                    div(mul(spread_amount, answer)?, decimals)?
+                };
+
+                div(result, SPREAD_DENOMINATOR.into())
+            }
+            AmountGetter::ChainlinkDoublePrice {
+                oracle1,
+                oracle2,
+                decimals_scale,
+                spread,
+                ..
+            } => {
+                let (answer1, _) = oracle_answer(oracle1, context)?;
+                let (answer2, _) = oracle_answer(oracle2, context)?;
+                let result = div(
+                    div(mul(mul(*amount, *spread)?, answer1)?, answer2)?,
+                    SPREAD_DENOMINATOR.into(),
+                )?;
+                let scale = exp10(decimals_scale.unsigned_abs())?;
+
+                if *decimals_scale > 0 {
+                    mul(result, scale)
+                } else {
+                    div(result, scale)
+                }
+            }
+        }
+    }
+}
+
+impl DutchAuction {
+    // linear interpolation between start and end taking amounts
+    pub fn taking_amount(&self, time: u64) -> Result<U256, String> {
+        if self.end_time <= self.start_time {
+            return Err("Dutch auction should end after start".to_string());
+        }
+
+        let time = time.clamp(self.start_time, self.end_time);
+
+        div(
+            add(
+                mul(self.taking_amount_start, (self.end_time - time).into())?,
+                mul(self.taking_amount_end, (time - self.start_time).into())?,
+            )?,
+            (self.end_time - self.start_time).into(),
+        )
+    }
+
+    fn to_tokens(&self) -> Vec<Token> {
+        let start_time_end_time = (U256::from(self.start_time) << DUTCH_AUCTION_START_TIME_SHIFT)
+            | U256::from(self.end_time);
+
+        uints(&[
+            start_time_end_time,
+            self.taking_amount_start,
+            self.taking_amount_end,
+            self.making_amount,
+        ])
+    }
+}
+
+fn prefix_param_types(signature: &str) -> Vec<ParamType> {
+    match signature {
+        GET_RANGE_TAKER_AMOUNT | GET_RANGE_MAKER_AMOUNT => vec![ParamType::Uint(256); 3],
+        SINGLE_PRICE => vec![ParamType::Address, ParamType::Uint(256)],
+        DOUBLE_PRICE => vec![
+            ParamType::Address,
+            ParamType::Address,
+            ParamType::Int(256),
+            ParamType::Uint(256),
+        ],
+        _ => vec![ParamType::Uint(256); 4],
+    }
+}
+
+fn uints(values: &[U256]) -> Vec<Token> {
+    values.iter().map(|value| Token::Uint(*value)).collect()
+}
+
+fn already_filled(order_making_amount: &U256, context: &GetterContext) -> Result<U256, String> {
+    order_making_amount
+        .checked_sub(context.remaining_making_amount)
+        .ok_or_else(|| "Remaining making amount exceeds order making amount".to_string())
+}
+
+fn price_delta(price_start: &U256, price_end: &U256) -> Result<U256, String> {
+    price_end
+        .checked_sub(*price_start)
+        .ok_or_else(|| "Incorrect range".to_string())
+}
+
+fn oracle_answer(oracle: &Address, context: &GetterContext) -> Result<(U256, U256), String> {
+    let answer = context
+        .oracles
+        .get(oracle)
+        .ok_or_else(|| format!("Missing answer of oracle {:?}", oracle))?;
+
+    Ok((answer.answer, exp10(answer.decimals as u64)?))
+}
+
+fn auction_time(value: U256) -> Result<u64, String> {
+    u64::try_from(value).map_err(|_| format!("Dutch auction time {} is out of range", value))
+}
+
+fn exp10(exponent: u64) -> Result<U256, String> {
+    U256::from(10)
+        .checked_pow(exponent.into())
+        .ok_or_else(overflow)
+}
+
+fn add(a: U256, b: U256) -> Result<U256, String> {
+    a.checked_add(b).ok_or_else(overflow)
+}
+
+fn mul(a: U256, b: U256) -> Result<U256, String> {
+    a.checked_mul(b).ok_or_else(overflow)
+}
+
+fn div(a: U256, b: U256) -> Result<U256, String> {
+    a.checked_div(b)
+        .ok_or_else(|| "Division by zero".to_string())
+}
+
+fn overflow() -> String {
+    "Amount overflow".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, str::FromStr};
+
+    use ethers::{
+        abi::{encode, Token},
+        types::{Address, Bytes, I256, U256},
+        utils::id,
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        constants::{DOUBLE_PRICE, DUTCH_AUCTION_GET_TAKING_AMOUNT},
+        types::{AmountGetter, DutchAuction, GetterContext, OracleAnswer},
+    };
+
+    fn calculator() -> Address {
+        Address::from_str("0x1111111254eeb25477b68fb85ed929f73a960582").unwrap()
+    }
+
+    fn oracle(n: u64) -> Address {
+        Address::from_low_u64_be(n)
+    }
+
+    fn ether(n: u64) -> U256 {
+        U256::exp10(18) * n
+    }
+
+    fn context(remaining_making_amount: U256) -> GetterContext {
+        GetterContext {
+            time: 1673548200,
+            remaining_making_amount,
+            oracles: HashMap::from([
+                (
+                    oracle(1),
+                    OracleAnswer {
+                        answer: U256::from(1500) * U256::exp10(8),
+                        decimals: 8,
+                    },
+                ),
+                (
+                    oracle(2),
+                    OracleAnswer {
+                        answer: U256::exp10(8),
+                        decimals: 8,
+                    },
+                ),
+            ]),
+        }
+    }
+
+    fn range(taking: bool) -> AmountGetter {
+        let (price_start, price_end, order_making_amount) = (ether(1000), ether(2000), ether(10));
+
+        if taking {
+            AmountGetter::RangeTakingAmount {
+                calculator: calculator(),
+                price_start,
+                price_end,
+                order_making_amount,
+            }
+        } else {
+            AmountGetter::RangeMakingAmount {
+                calculator: calculator(),
+                price_start,
+                price_end,
+                order_making_amount,
+            }
+        }
+    }
+
+    fn dutch_auction() -> DutchAuction {
+        DutchAuction {
+            start_time: 1673548100,
+            end_time: 1673548300,
+            taking_amount_start: U256::from(2000),
+            taking_amount_end: U256::from(1000),
+            making_amount: U256::from(100),
+        }
+    }
+
+    #[test]
+    fn should_pack_getter_as_target_and_calldata() {
+        let getter = AmountGetter::ChainlinkSinglePrice {
+            calculator: calculator(),
+            oracle: oracle(1),
+            inverse: true,
+            spread: U256::from(990000000),
+        };
+
+        assert_eq!(
+            getter.build(),
+            Bytes::from_str(&format!(
+                "0x{}{}{}{}",
+                "1111111254eeb25477b68fb85ed929f73a960582",
+                hex::encode(ethers::utils::id("singlePrice(address,uint256,uint256)")),
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "800000000000000000000000000000000000000000000000000000003b023380"
+            ))
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn should_roundtrip_getters() {
+        let getters = [
+            range(true),
+            range(false),
+            AmountGetter::DutchAuctionTakingAmount {
+                calculator: calculator(),
+                auction: dutch_auction(),
+            },
+            AmountGetter::DutchAuctionMakingAmount {
+                calculator: calculator(),
+                auction: dutch_auction(),
+            },
+            AmountGetter::ChainlinkSinglePrice {
+                calculator: calculator(),
+                oracle: oracle(1),
+                inverse: false,
+                spread: U256::from(1010000000),
+            },
+            AmountGetter::ChainlinkDoublePrice {
+                calculator: calculator(),
+                oracle1: oracle(1),
+                oracle2: oracle(2),
+                decimals_scale: -12,
+                spread: U256::from(1000000000),
+            },
+        ];
+
+        for getter in getters {
+            assert_eq!(AmountGetter::decode(&getter.build()), Ok(getter));
+        }
+    }
+
+    #[test]
+    fn should_evaluate_range_getters() {
+        // first ether is sold for 1050 on average, the next one for 1150
+        assert_eq!(
+            range(true).evaluate(&ether(1), &context(ether(10))),
+            Ok(ether(1050))
+        );
+        assert_eq!(
+            range(true).evaluate(&ether(1), &context(ether(9))),
+            Ok(ether(1150))
+        );
+        assert_eq!(
+            range(false).evaluate(&ether(1150), &context(ether(9))),
+            Ok(ether(1))
+        );
+        assert_eq!(
+            range(false).evaluate(&ether(1050), &context(ether(11))),
+            Err("Remaining making amount exceeds order making amount".to_string())
+        );
+    }
+
+    #[test]
+    fn should_evaluate_dutch_auction_getters() {
+        let taking = AmountGetter::DutchAuctionTakingAmount {
+            calculator: calculator(),
+            auction: dutch_auction(),
+        };
+        let making = AmountGetter::DutchAuctionMakingAmount {
+            calculator: calculator(),
+            auction: dutch_auction(),
+        };
+
+        // a half of the auction has passed
+        assert_eq!(
+            taking.evaluate(&U256::from(10), &context(U256::zero())),
+            Ok(U256::from(150))
+        );
+        assert_eq!(
+            making.evaluate(&U256::from(150), &context(U256::zero())),
+            Ok(U256::from(10))
+        );
+        assert_eq!(dutch_auction().taking_amount(0), Ok(U256::from(2000)));
+        assert_eq!(
+            dutch_auction().taking_amount(u64::MAX),
+            Ok(U256::from(1000))
+        );
+    }
+
+    #[test]
+    fn should_evaluate_chainlink_getters() {
+        let single = |inverse| AmountGetter::ChainlinkSinglePrice {
+            calculator: calculator(),
+            oracle: oracle(1),
+            inverse,
+            spread: U256::from(1000000000),
+        };
+
+        assert_eq!(
+            single(false).evaluate(&ether(2), &context(U256::zero())),
+            Ok(ether(3000))
+        );
+        assert_eq!(
+            single(true).evaluate(&ether(3000), &context(U256::zero())),
+            Ok(ether(2))
+        );
+        assert_eq!(
+            AmountGetter::ChainlinkDoublePrice {
+                calculator: calculator(),
+                oracle1: oracle(1),
+                oracle2: oracle(2),
+                decimals_scale: -12,
+                spread: U256::from(990000000),
+            }
+            .evaluate(&ether(1), &context(U256::zero())),
+            Ok(U256::from(1485000000))
+        );
+        assert_eq!(
+            single(false).evaluate(
+                &ether(1),
+                &GetterContext {
+                    oracles: HashMap::new(),
+                    ..context(U256::zero())
+                }
+            ),
+            Err("Missing answer of oracle 0x0000000000000000000000000000000000000001".to_string())
+        );
+    }
+
+    #[test]
+    fn should_refuse_division_by_zero() {
+        let zero_making = AmountGetter::RangeTakingAmount {
+            calculator: calculator(),
+            price_start: ether(1000),
+            price_end: ether(2000),
+            order_making_amount: U256::zero(),
+        };
+        let zero_taking = AmountGetter::DutchAuctionMakingAmount {
+            calculator: calculator(),
+            auction: DutchAuction {
+                taking_amount_start: U256::zero(),
+                taking_amount_end: U256::zero(),
+                ..dutch_auction()
+            },
+        };
+        let zero_answer = GetterContext {
+            oracles: HashMap::from([(
+                oracle(1),
+                OracleAnswer {
+                    answer: U256::zero(),
+                    decimals: 8,
+                },
+            )]),
+            ..context(U256::zero())
+        };
+
+        assert_eq!(
+            zero_making.evaluate(&ether(1), &context(U256::zero())),
+            Err("Division by zero".to_string())
+        );
+        assert_eq!(
+            zero_taking.evaluate(&U256::from(150), &context(U256::zero())),
+            Err("Division by zero".to_string())
+        );
+        assert_eq!(
+            AmountGetter::ChainlinkSinglePrice {
+                calculator: calculator(),
+                oracle: oracle(1),
+                inverse: true,
+                spread: U256::from(1000000000),
+            }
+            .evaluate(&ether(1), &zero_answer),
+            Err("Division by zero".to_string())
+        );
+    }
+
+    #[test]
+    fn should_refuse_overflow() {
+        let single = AmountGetter::ChainlinkSinglePrice {
+            calculator: calculator(),
+            oracle: oracle(1),
+            inverse: false,
+            spread: U256::from(1000000000),
+        };
+        let double = AmountGetter::ChainlinkDoublePrice {
+            calculator: calculator(),
+            oracle1: oracle(1),
+            oracle2: oracle(2),
+            decimals_scale: 100,
+            spread: U256::from(1000000000),
+        };
+
+        assert_eq!(
+            single.evaluate(&U256::MAX, &context(U256::zero())),
+            Err("Amount overflow".to_string())
+        );
+        assert_eq!(
+            double.evaluate(&ether(1), &context(U256::zero())),
+            Err("Amount overflow".to_string())
+        );
+        assert_eq!(
+            range(true).evaluate(&U256::MAX, &context(ether(10))),
+            Err("Amount overflow".to_string())
+        );
+    }
+
+    #[test]
+    fn should_refuse_out_of_range_getter_arguments() {
+        let getter = |signature: &str, tokens: &[Token]| -> Bytes {
+            [calculator().as_bytes(), &id(signature), &encode(tokens)]
+                .concat()
+                .into()
+        };
+
+        let dutch_auction = getter(
+            DUTCH_AUCTION_GET_TAKING_AMOUNT,
+            &[
+                Token::Uint(U256::from(u128::MAX) << 128),
+                Token::Uint(U256::from(2000)),
+                Token::Uint(U256::from(1000)),
+                Token::Uint(U256::from(100)),
+            ],
+        );
+        let double_price = getter(
+            DOUBLE_PRICE,
+            &[
+                Token::Address(oracle(1)),
+                Token::Address(oracle(2)),
+                Token::Int(I256::from(i128::MIN).into_raw()),
+                Token::Uint(U256::from(1000000000)),
+            ],
+        );
+
+        assert_eq!(
+            AmountGetter::decode(&dutch_auction),
+            Err(format!("Dutch auction time {} is out of range", u128::MAX))
+        );
+        assert_eq!(
+            AmountGetter::decode(&double_price),
+            Err(format!("Decimals scale {} is out of range", i128::MIN))
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use ethers::types::{Address, U256};
+
+// static call prefix of getMakingAmount/getTakingAmount interaction,
+// the protocol appends requested amount and remaining making amount to it
+#[derive(Debug, Clone, PartialEq)]
+pub enum AmountGetter {
+    RangeTakingAmount {
+        calculator: Address,
+        price_start: U256,
+        price_end: U256,
+        order_making_amount: U256,
+    },
+    RangeMakingAmount {
+        calculator: Address,
+        price_start: U256,
+        price_end: U256,
+        order_making_amount: U256,
+    },
+    DutchAuctionTakingAmount {
+        calculator: Address,
+        auction: DutchAuction,
+    },
+    DutchAuctionMakingAmount {
+        calculator: Address,
+        auction: DutchAuction,
+    },
+    ChainlinkSinglePrice {
+        calculator: Address,
+        oracle: Address,
+        inverse: bool,
+        spread: U256,
+    },
+    ChainlinkDoublePrice {
+        calculator: Address,
+        oracle1: Address,
+        oracle2: Address,
+        decimals_scale: i64,
+        spread: U256,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DutchAuction {
+    pub start_time: u64,
+    pub end_time: u64,
+    pub taking_amount_start: U256,
+    pub taking_amount_end: U256,
+    pub making_amount: U256,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OracleAnswer {
+    pub answer: U256,
+    pub decimals: u32,
+}
+
+// on-chain state getters depend on
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetterContext {
+    pub time: u64,
+    pub remaining_making_amount: U256,
+    pub oracles: HashMap<Address, OracleAnswer>,
+}
@@ -12,15 +12,21 @@ use crate::{
 };
 use calc::linear_interpolation;
 use constants::RATE_BUMP_DENOMINATOR;
-use ethers::types::U256;
+use ethers::types::{Address, U256};
 use types::AuctionCalculator;
 
 impl AuctionCalculator {
-    pub fn from_limit_order_v3_struct(order: LimitOrderV3Struct) -> Self {
-        let suffix = AuctionSuffix::decode(&order.interactions);
-        let salt = AuctionSalt::decode(&order.salt);
-
-        AuctionCalculator::from_auction_data(&suffix, &salt)
+    // suffix is read from the post interaction, which has to target settlement
+    pub fn from_limit_order_v3_struct(
+        order: &LimitOrderV3Struct,
+        settlement: &Address,
+    ) -> Result<Self, String> {
+        let post_interaction = AuctionSuffix::from_limit_order_v3_struct(order, settlement)?;
+
+        Ok(AuctionCalculator::from_auction_data(
+            &post_interaction.suffix,
+            &AuctionSalt::decode(&order.salt),
+        ))
     }
 
     pub fn from_auction_data(suffix: &AuctionSuffix, salt: &AuctionSalt) -> Self {
@@ -34,15 +40,23 @@ impl AuctionCalculator {
     }
 
     pub fn calc_auction_taking_amount(&self, taking_amount: &U256, rate: u32) -> U256 {
-        let auction_taking_amount = (taking_amount
-            * (U256::from(rate) + U256::from(RATE_BUMP_DENOMINATOR)))
+        self.checked_auction_taking_amount(taking_amount, rate)
+            .expect("auction taking amount overflow")
+    }
+
+    // None when the amount doesn't fit into uint256
+    pub fn checked_auction_taking_amount(&self, taking_amount: &U256, rate: u32) -> Option<U256> {
+        let auction_taking_amount = taking_amount
+            .checked_mul(U256::from(rate) + U256::from(RATE_BUMP_DENOMINATOR))?
             / U256::from(RATE_BUMP_DENOMINATOR);
 
         if self.taker_fee_ratio == U256::zero() {
-            auction_taking_amount
+            Some(auction_taking_amount)
         } else {
-            auction_taking_amount
-                + (auction_taking_amount * self.taker_fee_ratio / CONTRACT_TAKER_FEE_PRECISION)
+            auction_taking_amount.checked_add(
+                auction_taking_amount.checked_mul(self.taker_fee_ratio)?
+                    / CONTRACT_TAKER_FEE_PRECISION,
+            )
         }
     }
 
@@ -114,21 +128,23 @@ mod tests {
 
     use crate::auction_suffix::types::AuctionSuffix;
     use crate::limit_order::types::LimitOrderV3Struct;
+    use crate::test_utils::settlement;
 
     use super::types::AuctionCalculator;
-    #[test]
-    fn should_calculate_auction_rate_and_taking_amount() {
-        let calculator = AuctionCalculator::from_limit_order_v3_struct(LimitOrderV3Struct {
-            allowed_sender: Address::from_str("0x0000000000000000000000000000000000000000")
-                .unwrap(),
+
+    // post interaction is settlement address followed by auction suffix
+    fn order() -> LimitOrderV3Struct {
+        LimitOrderV3Struct {
+            allowed_sender: settlement(),
             interactions: Bytes::from_str(
-                "0x000c004e200000000000000000219ab540356cbb839cbe05303d7705faf486570009",
+                "0xa88800cd213da5ae406ce248380802bd53b47647\
+                000c004e200000000000000000219ab540356cbb839cbe05303d7705faf486570009",
             )
             .unwrap(),
             maker: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
             maker_asset: Address::from_str("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2").unwrap(),
             making_amount: U256::from(1000000000000000000 as u64),
-            offsets: U256::zero(),
+            offsets: U256::from(54) << 224,
             receiver: Address::from_str("0x0000000000000000000000000000000000000000").unwrap(),
             salt: U256::from_dec_str(
                 "45118768841948961586167738353692277076075522015101619148498725069326976558864",
@@ -136,7 +152,13 @@ mod tests {
             .unwrap(),
             taker_asset: Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap(),
             taking_amount: U256::from(1420000000),
-        });
+        }
+    }
+
+    #[test]
+    fn should_calculate_auction_rate_and_taking_amount() {
+        let calculator =
+            AuctionCalculator::from_limit_order_v3_struct(&order(), &settlement()).unwrap();
 
         let rate = calculator.calc_rate_bump(1673548209);
 
@@ -147,6 +169,29 @@ mod tests {
         assert_eq!(auction_taking_amount, U256::from(1422028470));
     }
 
+    #[test]
+    fn should_read_suffix_from_post_interaction_only() {
+        // the same bytes placed into pre interaction are not a suffix
+        let pre_interaction_order = LimitOrderV3Struct {
+            offsets: (U256::from(54) << 192) | (U256::from(54) << 224),
+            ..order()
+        };
+        let other_settlement = Address::from_low_u64_be(1);
+
+        assert_eq!(
+            AuctionCalculator::from_limit_order_v3_struct(&pre_interaction_order, &settlement()),
+            Err("Post interaction is too short".to_string())
+        );
+        assert_eq!(
+            AuctionCalculator::from_limit_order_v3_struct(&order(), &other_settlement),
+            Err(format!(
+                "Post interaction target {:?} is not settlement contract {:?}",
+                settlement(),
+                other_settlement
+            ))
+        );
+    }
+
     #[test]
     fn should_be_created_successfully_from_suffix_and_salt() {
         let suffix = AuctionSuffix::decode(
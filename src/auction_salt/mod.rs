@@ -1,29 +1,22 @@
 pub mod parser;
 pub mod types;
 
-use crate::{constants::ZX, salt::build_salt, utils::PadStart};
+use crate::{constants::ZX, salt::SaltGenerator, utils::PadStart};
 use ethers::{abi::AbiEncode, types::U256};
 use parser::{constants::salt_mask, *};
 use std::{borrow::Borrow, str::FromStr};
 use types::{AuctionSalt, AuctionSaltData};
 
 impl AuctionSalt {
-    pub fn new<F>(auction: AuctionSaltData, salt_generator: Option<F>) -> Self
+    pub fn new<G>(auction: AuctionSaltData, salt_generator: &G) -> Self
     where
-        F: Fn() -> U256,
+        G: SaltGenerator + ?Sized,
     {
-        let salt = if let Some(salt) = auction.salt {
-            if salt_mask().lt(&salt) {
-                panic!("salt should be less than 18 bytes");
-            }
-            salt
-        } else {
-            if let Some(salt_generator) = salt_generator {
-                salt_generator()
-            } else {
-                build_salt()
-            }
-        };
+        let salt = auction.salt.unwrap_or_else(|| salt_generator.generate());
+
+        if salt_mask().lt(&salt) {
+            panic!("salt should be less than 18 bytes");
+        }
 
         Self {
             salt,
@@ -74,7 +67,7 @@ impl AuctionSalt {
 
 #[cfg(test)]
 mod tests {
-    use crate::auction_salt::types::AuctionSalt;
+    use crate::{auction_salt::types::AuctionSalt, salt::DeterministicSalt};
 
     use super::AuctionSaltData;
     use ethers::core::types::U256;
@@ -90,7 +83,7 @@ mod tests {
                 bank_fee: U256::from(0),
                 salt: None,
             },
-            Some(|| U256::from(1000)),
+            &|| U256::from(1000),
         );
 
         assert_eq!(
@@ -110,7 +103,7 @@ mod tests {
                 bank_fee: U256::from(123123123),
                 salt: None,
             },
-            Some(|| U256::from(1000)),
+            &|| U256::from(1000),
         );
 
         assert_eq!(
@@ -120,6 +113,28 @@ mod tests {
         )
     }
 
+    #[test]
+    fn should_create_same_salt_with_deterministic_strategy() {
+        let auction = AuctionSaltData {
+            auction_start_time: 1673548149,
+            initial_rate_bump: 50000,
+            duration: 180,
+            bank_fee: U256::from(0),
+            salt: None,
+        };
+
+        let salt = AuctionSalt::new(auction.clone(), &DeterministicSalt::new(b"fusion"));
+
+        assert_eq!(
+            salt,
+            AuctionSalt::new(auction, &DeterministicSalt::new(b"fusion"))
+        );
+        assert_eq!(
+            AuctionSalt::decode(&U256::from_dec_str(&salt.build()).unwrap()),
+            salt
+        );
+    }
+
     #[test]
     #[should_panic(expected = "initial_rate_bump is too big, should be less than 2^24")]
     fn should_fail_to_create_salt_due_to_initial_rate_bump_out_of_range() {
@@ -131,7 +146,7 @@ mod tests {
                 bank_fee: U256::from(123123123),
                 salt: None,
             },
-            Some(|| U256::from(1000)),
+            &|| U256::from(1000),
         );
 
         salt.build();
@@ -148,7 +163,7 @@ mod tests {
                 bank_fee: U256::from(123123123),
                 salt: None,
             },
-            Some(|| U256::from(1000)),
+            &|| U256::from(1000),
         );
 
         salt.build();
@@ -1,7 +1,9 @@
 pub mod constants;
 pub mod encoder;
 pub mod parser;
+pub mod post_interaction;
 pub mod types;
+pub mod whitelist;
 
 use crate::{
     auction_suffix::encoder::{
@@ -37,6 +39,8 @@ impl AuctionSuffix {
         }
     }
 
+    // decodes the suffix at the tail of given bytes, orders should go through
+    // from_limit_order_v3_struct which finds it in the settlement post interaction
     pub fn decode(interactions: &Bytes) -> Self {
         let suffix = parse_interactions_suffix(interactions);
 
@@ -47,6 +47,43 @@ pub fn parse_interactions_suffix(interactions: &Bytes) -> InteractionAdditionalI
     }
 }
 
+// checks everything parse_interactions_suffix panics on
+pub fn validate_interactions_suffix(interactions: &Bytes) -> Result<(), String> {
+    if interactions.is_empty() {
+        return Err("Empty interactions".to_string());
+    }
+
+    let flags = interactions[interactions.len() - 1] as usize;
+
+    if (flags & RESOLVERS_LENGTH_MASK) >> RESOLVERS_LENGTH_OFFSET == 0 {
+        return Err("Cannot have 0 resolvers".to_string());
+    }
+
+    let flags = parse_flags(interactions);
+
+    if interactions.len() < min_interactions_length(&flags) {
+        return Err("Wrong interactions length".to_string());
+    }
+
+    if flags.taking_fee_enabled {
+        let fee_end = interactions.len() - FLAGS_LENGTH;
+        let fee_start = fee_end - TAKER_FEE_RECEIVER_LENGTH - TAKER_FEE_RATIO_LENGTH;
+        let fee_data = &interactions[fee_start..fee_end];
+
+        if Address::from_slice(&fee_data[TAKER_FEE_RATIO_LENGTH..]) == ZERO_ADDRESS {
+            return Err("taker_fee_receiver cannot be zero address".to_string());
+        }
+
+        if U256::from(&fee_data[0..TAKER_FEE_RATIO_LENGTH])
+            > U256::from(CONTRACT_TAKER_FEE_PRECISION)
+        {
+            return Err("taker_fee_ratio cannot be greater than 100%".to_string());
+        }
+    }
+
+    Ok(())
+}
+
 pub fn parse_taking_fee_and_return_remaining_interactions(
     flags: &InteractionFlags,
     interactions: &[u8],
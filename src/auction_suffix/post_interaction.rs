@@ -0,0 +1,237 @@
+use std::str::FromStr;
+
+use ethers::types::{Address, Bytes};
+
+use crate::limit_order::{
+    interactions::Interaction,
+    parser::{parse_interaction_field, validate_offsets},
+    types::LimitOrderV3Struct,
+};
+
+use super::{
+    parser::{min_interactions_length, parse_flags, validate_interactions_suffix},
+    types::{AuctionSuffix, PostInteractionData},
+};
+
+// index of post interaction in INTERACTIONS_LAYOUT
+const POST_INTERACTION_FIELD: u8 = 7;
+
+const TARGET_LENGTH: usize = 20;
+
+// post interaction of fusion order is settlement address | user interaction | suffix,
+// the protocol calls settlement with everything after the address
+impl AuctionSuffix {
+    pub fn from_limit_order_v3_struct(
+        order: &LimitOrderV3Struct,
+        settlement: &Address,
+    ) -> Result<PostInteractionData, String> {
+        validate_offsets(&order.offsets, &order.interactions)?;
+
+        let post_interaction =
+            parse_interaction_field(&order.offsets, &order.interactions, POST_INTERACTION_FIELD);
+
+        Self::from_post_interaction(&post_interaction, settlement)
+    }
+
+    pub fn from_post_interaction(
+        post_interaction: &Bytes,
+        settlement: &Address,
+    ) -> Result<PostInteractionData, String> {
+        if post_interaction.len() < TARGET_LENGTH {
+            return Err("Post interaction is too short".to_string());
+        }
+
+        let (target, data) = post_interaction.split_at(TARGET_LENGTH);
+        let target = Address::from_slice(target);
+
+        if target != *settlement {
+            return Err(format!(
+                "Post interaction target {:?} is not settlement contract {:?}",
+                target, settlement
+            ));
+        }
+
+        let data = Bytes::from(data.to_vec());
+        validate_interactions_suffix(&data)?;
+
+        let suffix_start = data.len() - min_interactions_length(&parse_flags(&data));
+
+        Ok(PostInteractionData {
+            interaction: Bytes::from(data[..suffix_start].to_vec()),
+            suffix: AuctionSuffix::decode(&data),
+        })
+    }
+}
+
+impl PostInteractionData {
+    pub fn build(&self, settlement: &Address) -> Bytes {
+        [
+            settlement.as_bytes(),
+            &self.interaction,
+            &Bytes::from_str(&self.suffix.build()).unwrap(),
+        ]
+        .concat()
+        .into()
+    }
+
+    // user interaction encoded as a chain of interactions
+    pub fn interactions(&self) -> Result<Vec<Interaction>, String> {
+        Interaction::decode_list(&self.interaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::types::{Address, Bytes, U256};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        auction_calculator::types::AuctionCalculator,
+        auction_salt::types::AuctionSalt,
+        auction_suffix::{
+            parser::types::AuctionPoint,
+            types::{AuctionSuffix, PostInteractionData, SettlementSuffixData},
+            whitelist::WhitelistBuilder,
+        },
+        limit_order::{
+            interactions::Interaction, predicate::Predicate, InteractionsData, LimitOrder,
+            OrderInfoData,
+        },
+        test_utils::settlement,
+    };
+
+    fn suffix() -> AuctionSuffix {
+        AuctionSuffix::new(SettlementSuffixData {
+            points: vec![AuctionPoint {
+                coefficient: 20000,
+                delay: 12,
+            }],
+            whitelist: WhitelistBuilder::new(1673548139)
+                .add_resolver(Address::from_low_u64_be(0x1000))
+                .build()
+                .unwrap(),
+            public_resolving_deadline: Some(1673548139),
+            fee: None,
+        })
+    }
+
+    fn post_interaction_data() -> PostInteractionData {
+        PostInteractionData {
+            interaction: Interaction::encode_list(&[Interaction::new(
+                Address::from_low_u64_be(0xcafe),
+                Bytes::from_str("0xdeadbeef").unwrap(),
+            )]),
+            suffix: AuctionSuffix::decode(&Bytes::from_str(&suffix().build()).unwrap()),
+        }
+    }
+
+    fn order(post_interaction: Bytes) -> LimitOrder {
+        LimitOrder::new(
+            &OrderInfoData {
+                maker_asset: Address::from_low_u64_be(0xaa),
+                taker_asset: Address::from_low_u64_be(0xbb),
+                making_amount: U256::from(1000),
+                taking_amount: U256::from(2000),
+                maker: Address::from_low_u64_be(1),
+                salt: Some(
+                    U256::from_dec_str(
+                        "45118768841948961586167738353692277076075522015101619148498725069326976558864",
+                    )
+                    .unwrap(),
+                ),
+                allowed_sender: Some(settlement()),
+                receiver: None,
+            },
+            Some(&InteractionsData {
+                predicate: Some(Predicate::TimestampBelow(1673548339).build()),
+                post_interaction: Some(post_interaction),
+                ..Default::default()
+            }),
+            &|| U256::zero(),
+        )
+    }
+
+    #[test]
+    fn should_extract_suffix_and_user_interaction() {
+        let data = post_interaction_data();
+        let order = order(data.build(&settlement())).build();
+
+        let extracted = AuctionSuffix::from_limit_order_v3_struct(&order, &settlement()).unwrap();
+
+        assert_eq!(extracted, data);
+        assert_eq!(
+            extracted.interactions(),
+            Ok(vec![Interaction::new(
+                Address::from_low_u64_be(0xcafe),
+                Bytes::from_str("0xdeadbeef").unwrap(),
+            )])
+        );
+        assert_eq!(
+            AuctionCalculator::from_limit_order_v3_struct(&order, &settlement()),
+            Ok(AuctionCalculator::from_auction_data(
+                &data.suffix,
+                &AuctionSalt::decode(&order.salt)
+            ))
+        );
+    }
+
+    #[test]
+    fn should_extract_suffix_without_user_interaction() {
+        let data = PostInteractionData {
+            interaction: Bytes::default(),
+            ..post_interaction_data()
+        };
+
+        assert_eq!(
+            AuctionSuffix::from_post_interaction(&data.build(&settlement()), &settlement()),
+            Ok(data)
+        );
+    }
+
+    #[test]
+    fn should_refuse_foreign_post_interaction() {
+        let other = Address::from_low_u64_be(0xbad);
+        let order = order(post_interaction_data().build(&other)).build();
+
+        assert_eq!(
+            AuctionSuffix::from_limit_order_v3_struct(&order, &settlement()),
+            Err(format!(
+                "Post interaction target {:?} is not settlement contract {:?}",
+                other,
+                settlement()
+            ))
+        );
+        assert_eq!(
+            AuctionSuffix::from_post_interaction(&Bytes::from(vec![0; 19]), &settlement()),
+            Err("Post interaction is too short".to_string())
+        );
+        assert_eq!(
+            AuctionSuffix::from_post_interaction(
+                &Bytes::from(settlement().as_bytes().to_vec()),
+                &settlement()
+            ),
+            Err("Empty interactions".to_string())
+        );
+    }
+
+    #[test]
+    fn should_validate_suffix_of_post_interaction() {
+        let post_interaction =
+            |suffix: &str| Bytes::from_str(&format!("{:x}{}", settlement(), suffix)).unwrap();
+
+        assert_eq!(
+            AuctionSuffix::from_post_interaction(&post_interaction("00"), &settlement()),
+            Err("Cannot have 0 resolvers".to_string())
+        );
+        assert_eq!(
+            AuctionSuffix::from_post_interaction(&post_interaction("63c0566a09"), &settlement()),
+            Err("Wrong interactions length".to_string())
+        );
+        assert_eq!(
+            AuctionSuffix::from_post_interaction(&post_interaction("000c004e200000000000000000219ab540356cbb839cbe05303d7705fa63c0566a00000000000000000000006400000000000000000000000000000000000000000089"), &settlement()),
+            Err("taker_fee_receiver cannot be zero address".to_string())
+        );
+    }
+}
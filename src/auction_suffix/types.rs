@@ -1,5 +1,5 @@
-use ethers::types::Address;
 use ethers::types::U256;
+use ethers::types::{Address, Bytes};
 
 use super::parser::types::AuctionPoint;
 use super::parser::types::AuctionWhitelistItem;
@@ -25,3 +25,10 @@ pub struct SettlementSuffixData {
     pub public_resolving_deadline: Option<u32>,
     pub fee: Option<TakingFee>,
 }
+
+// post interaction of fusion order split into its parts
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostInteractionData {
+    pub interaction: Bytes, // user interaction between settlement address and suffix
+    pub suffix: AuctionSuffix,
+}
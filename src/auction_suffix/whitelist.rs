@@ -0,0 +1,234 @@
+use ethers::types::Address;
+
+use super::{parser::types::AuctionWhitelistItem, types::AuctionSuffix};
+
+// resolvers count is stored in 4 bits of the suffix flags
+pub const MAX_RESOLVERS_COUNT: usize = 15;
+
+// mirrors OrderSuffix.checkResolver of the settlement contract:
+// after public_resolving_deadline any resolver can fill the order,
+// before it only whitelisted resolvers whose allowance has passed
+impl AuctionSuffix {
+    pub fn is_public(&self, time: u64) -> bool {
+        time > self.public_resolving_deadline.as_u64()
+    }
+
+    pub fn can_fill(&self, resolver: &Address, time: u64) -> bool {
+        self.is_public(time)
+            || self
+                .whitelist_item(resolver)
+                .is_some_and(|item| time >= item.allowance as u64)
+    }
+
+    // first timestamp at which resolver is able to fill the order
+    pub fn access_time(&self, resolver: &Address) -> u64 {
+        let public_time = self.public_resolving_deadline.as_u64() + 1;
+
+        self.whitelist_item(resolver)
+            .map_or(public_time, |item| public_time.min(item.allowance as u64))
+    }
+
+    // whitelisted resolvers able to fill the order while it is not public yet
+    pub fn exclusive_resolvers(&self, time: u64) -> Vec<Address> {
+        if self.is_public(time) {
+            return vec![];
+        }
+
+        self.whitelist
+            .iter()
+            .filter(|item| time >= item.allowance as u64)
+            .map(|item| item.address)
+            .collect()
+    }
+
+    fn whitelist_item(&self, resolver: &Address) -> Option<&AuctionWhitelistItem> {
+        self.whitelist.iter().find(|item| &item.address == resolver)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhitelistBuilder {
+    start_time: u32,
+    step: u32,
+    resolvers: Vec<Address>,
+}
+
+// assigns staggered allowances to ranked resolvers:
+// resolvers[0] is allowed at start_time, resolvers[1] at start_time + step and so on
+impl WhitelistBuilder {
+    pub fn new(start_time: u32) -> Self {
+        WhitelistBuilder {
+            start_time,
+            step: 0,
+            resolvers: vec![],
+        }
+    }
+
+    pub fn with_step(mut self, step: u32) -> Self {
+        self.step = step;
+
+        self
+    }
+
+    pub fn add_resolver(mut self, resolver: Address) -> Self {
+        if self.resolvers.contains(&resolver) {
+            panic!("resolver {:?} is already in whitelist", resolver);
+        }
+
+        if self.resolvers.len() == MAX_RESOLVERS_COUNT {
+            panic!(
+                "resolvers count cannot be greater than {}",
+                MAX_RESOLVERS_COUNT
+            );
+        }
+
+        self.resolvers.push(resolver);
+
+        self
+    }
+
+    pub fn with_resolvers(self, resolvers: &[Address]) -> Self {
+        resolvers
+            .iter()
+            .fold(self, |builder, resolver| builder.add_resolver(*resolver))
+    }
+
+    pub fn build(&self) -> Result<Vec<AuctionWhitelistItem>, String> {
+        self.resolvers
+            .iter()
+            .enumerate()
+            .map(|(rank, address)| {
+                let allowance = self
+                    .step
+                    .checked_mul(rank as u32)
+                    .and_then(|delay| self.start_time.checked_add(delay))
+                    .ok_or_else(|| {
+                        format!(
+                            "allowance of resolver {:?} does not fit into uint32",
+                            address
+                        )
+                    })?;
+
+                Ok(AuctionWhitelistItem {
+                    address: *address,
+                    allowance,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::Address;
+    use pretty_assertions::assert_eq;
+
+    use crate::auction_suffix::{
+        parser::types::{AuctionPoint, AuctionWhitelistItem},
+        types::{AuctionSuffix, SettlementSuffixData},
+    };
+
+    use super::WhitelistBuilder;
+
+    fn resolver(n: u64) -> Address {
+        Address::from_low_u64_be(n)
+    }
+
+    fn suffix() -> AuctionSuffix {
+        AuctionSuffix::new(SettlementSuffixData {
+            points: vec![AuctionPoint {
+                coefficient: 20000,
+                delay: 12,
+            }],
+            whitelist: WhitelistBuilder::new(1673548200)
+                .with_step(30)
+                .with_resolvers(&[resolver(1), resolver(2)])
+                .build()
+                .unwrap(),
+            public_resolving_deadline: Some(1673548300),
+            fee: None,
+        })
+    }
+
+    #[test]
+    fn should_build_staggered_whitelist() {
+        assert_eq!(
+            suffix().whitelist,
+            vec![
+                AuctionWhitelistItem {
+                    address: resolver(1),
+                    allowance: 1673548200,
+                },
+                AuctionWhitelistItem {
+                    address: resolver(2),
+                    allowance: 1673548230,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_check_resolver_access() {
+        let suffix = suffix();
+
+        assert!(!suffix.can_fill(&resolver(1), 1673548199));
+        assert!(suffix.can_fill(&resolver(1), 1673548200));
+        assert!(!suffix.can_fill(&resolver(2), 1673548229));
+        assert!(suffix.can_fill(&resolver(2), 1673548230));
+        assert!(!suffix.can_fill(&resolver(3), 1673548300));
+        assert!(suffix.can_fill(&resolver(3), 1673548301));
+    }
+
+    #[test]
+    fn should_compute_access_time() {
+        let suffix = suffix();
+
+        assert_eq!(suffix.access_time(&resolver(1)), 1673548200);
+        assert_eq!(suffix.access_time(&resolver(2)), 1673548230);
+        assert_eq!(suffix.access_time(&resolver(3)), 1673548301);
+    }
+
+    #[test]
+    fn should_list_exclusive_resolvers() {
+        let suffix = suffix();
+
+        assert_eq!(suffix.exclusive_resolvers(1673548100), vec![]);
+        assert_eq!(suffix.exclusive_resolvers(1673548210), vec![resolver(1)]);
+        assert_eq!(
+            suffix.exclusive_resolvers(1673548300),
+            vec![resolver(1), resolver(2)]
+        );
+        assert_eq!(suffix.exclusive_resolvers(1673548301), vec![]);
+    }
+
+    #[test]
+    fn should_refuse_allowance_overflow() {
+        assert_eq!(
+            WhitelistBuilder::new(u32::MAX - 10)
+                .with_step(20)
+                .with_resolvers(&[resolver(1), resolver(2)])
+                .build(),
+            Err(format!(
+                "allowance of resolver {:?} does not fit into uint32",
+                resolver(2)
+            ))
+        );
+        assert_eq!(
+            WhitelistBuilder::new(0)
+                .with_step(u32::MAX)
+                .with_resolvers(&[resolver(1), resolver(2), resolver(3)])
+                .build()
+                .map(|_| ()),
+            Err(format!(
+                "allowance of resolver {:?} does not fit into uint32",
+                resolver(3)
+            ))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "resolvers count cannot be greater than 15")]
+    fn should_limit_resolvers_count() {
+        WhitelistBuilder::new(0).with_resolvers(&(1..=16).map(resolver).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,482 @@
+use std::{path::Path, str::FromStr};
+
+use clap::Args;
+use ethers::{
+    signers::LocalWallet,
+    types::{transaction::eip712::TypedData, Address, Bytes, Signature, H256, U256},
+};
+use foo::{
+    auction_salt::{
+        parser::constants::salt_mask,
+        types::{AuctionSalt, AuctionSaltData},
+    },
+    auction_suffix::{
+        parser::types::{AuctionPoint, AuctionWhitelistItem},
+        types::{AuctionSuffix, PostInteractionData, SettlementSuffixData, TakingFee},
+    },
+    constants::{get_network_address, NetworkEnum, SETTLEMENT_CONTRACT_ADDRESS_MAP},
+    limit_order::{
+        eip712::order_typed_data_builder::get_limit_order_v3_domain, types::LimitOrderV3Struct,
+        InteractionsData, LimitOrder, OrderInfoData,
+    },
+    salt::RandomSalt,
+    utils::parse_u256,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{decode::render_order_lines, read_input, OutputFormat};
+
+#[derive(Args, Debug)]
+pub struct BuildArgs {
+    /// Path to order spec, toml or json
+    pub spec: String,
+
+    /// Sign order hash with the key from this keystore file
+    #[arg(long, value_name = "KEYSTORE")]
+    pub sign: Option<String>,
+
+    /// Keystore password
+    #[arg(long, env = "FUSION_KEYSTORE_PASSWORD", hide_env_values = true)]
+    pub password: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct OrderSpec {
+    pub chain_id: u64,
+    pub maker: Address,
+    pub maker_asset: Address,
+    pub taker_asset: Address,
+    pub making_amount: String,
+    pub taking_amount: String,
+    pub receiver: Option<Address>,
+    pub salt: Option<String>,
+    pub auction: AuctionSpec,
+    pub whitelist: Vec<WhitelistItemSpec>,
+    pub public_resolving_deadline: Option<u32>,
+    pub fee: Option<FeeSpec>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct AuctionSpec {
+    pub start_time: u64,
+    pub duration: u32,
+    pub initial_rate_bump: u32,
+    pub bank_fee: Option<String>,
+    #[serde(default)]
+    pub points: Vec<AuctionPointSpec>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct AuctionPointSpec {
+    pub delay: u16,
+    pub coefficient: u32,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct WhitelistItemSpec {
+    pub address: Address,
+    #[serde(default)]
+    pub allowance: u32,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct FeeSpec {
+    pub receiver: Address,
+    pub ratio: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuiltOrder {
+    pub order: LimitOrderV3Struct,
+    pub typed_data: TypedData,
+    pub order_hash: String,
+    pub signature: Option<Signature>,
+}
+
+pub fn run(args: &BuildArgs, output: OutputFormat) -> Result<String, String> {
+    let spec = parse_spec(&read_input(&args.spec)?, is_toml(&args.spec))?;
+    let mut built = build_order(&spec)?;
+
+    if let Some(keystore) = &args.sign {
+        let password = args
+            .password
+            .as_ref()
+            .ok_or("Keystore password is required to sign, use --password")?;
+        let wallet = LocalWallet::decrypt_keystore(keystore, password)
+            .map_err(|e| format!("Cannot decrypt keystore {}: {}", keystore, e))?;
+
+        built.signature = Some(sign_order_hash(&wallet, &built.order_hash)?);
+    }
+
+    Ok(match output {
+        OutputFormat::Human => render_human(&built),
+        OutputFormat::Json => serde_json::to_string_pretty(&render_json(&built)).unwrap(),
+    })
+}
+
+fn is_toml(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension == "toml")
+}
+
+pub fn parse_spec(input: &str, is_toml: bool) -> Result<OrderSpec, String> {
+    if is_toml {
+        toml::from_str(input).map_err(|e| format!("Invalid spec: {}", e))
+    } else {
+        serde_json::from_str(input).map_err(|e| format!("Invalid spec: {}", e))
+    }
+}
+
+pub fn build_order(spec: &OrderSpec) -> Result<BuiltOrder, String> {
+    let network = NetworkEnum::from_chain_id(spec.chain_id)
+        .ok_or_else(|| format!("Unsupported chain id {}", spec.chain_id))?;
+    let settlement = get_network_address(&SETTLEMENT_CONTRACT_ADDRESS_MAP, network);
+
+    if spec.whitelist.is_empty() {
+        return Err("Whitelist should contain at least one resolver".to_string());
+    }
+
+    if spec.auction.points.len() > 7 {
+        return Err("Auction cannot have more than 7 points".to_string());
+    }
+
+    let bank_fee = spec
+        .auction
+        .bank_fee
+        .as_deref()
+        .map_or(Ok(U256::zero()), parse_u256)?;
+    let salt = spec.salt.as_deref().map(parse_u256).transpose()?;
+
+    validate_auction_salt(spec, &bank_fee, salt.as_ref())?;
+
+    let auction_salt = AuctionSalt::new(
+        AuctionSaltData {
+            auction_start_time: spec.auction.start_time,
+            initial_rate_bump: spec.auction.initial_rate_bump,
+            duration: spec.auction.duration,
+            bank_fee,
+            salt,
+        },
+        &RandomSalt,
+    );
+
+    let suffix = AuctionSuffix::new(SettlementSuffixData {
+        points: spec
+            .auction
+            .points
+            .iter()
+            .map(|p| AuctionPoint {
+                delay: p.delay,
+                coefficient: p.coefficient,
+            })
+            .collect(),
+        whitelist: spec
+            .whitelist
+            .iter()
+            .map(|w| AuctionWhitelistItem {
+                address: w.address,
+                allowance: w.allowance,
+            })
+            .collect(),
+        public_resolving_deadline: spec.public_resolving_deadline,
+        fee: spec
+            .fee
+            .as_ref()
+            .map(|f| -> Result<TakingFee, String> {
+                Ok(TakingFee {
+                    taking_fee_receiver: f.receiver,
+                    taking_fee_ratio: parse_u256(&f.ratio)?,
+                })
+            })
+            .transpose()?,
+    });
+
+    let order = LimitOrder::new(
+        &OrderInfoData {
+            maker_asset: spec.maker_asset,
+            taker_asset: spec.taker_asset,
+            making_amount: parse_u256(&spec.making_amount)?,
+            taking_amount: parse_u256(&spec.taking_amount)?,
+            maker: spec.maker,
+            salt: Some(
+                U256::from_dec_str(&auction_salt.build())
+                    .map_err(|e| format!("Invalid auction salt: {}", e))?,
+            ),
+            allowed_sender: Some(settlement),
+            receiver: spec.receiver,
+        },
+        Some(&InteractionsData {
+            maker_asset_data: None,
+            taker_asset_data: None,
+            get_making_amount: None,
+            get_taking_amount: None,
+            predicate: None,
+            permit: None,
+            pre_interaction: None,
+            post_interaction: Some(
+                PostInteractionData {
+                    interaction: Bytes::default(),
+                    suffix,
+                }
+                .build(&settlement),
+            ),
+        }),
+        &RandomSalt,
+    );
+
+    let domain = get_limit_order_v3_domain(&U256::from(spec.chain_id));
+
+    Ok(BuiltOrder {
+        order: order.build(),
+        typed_data: order.get_typed_data(Some(&domain)),
+        order_hash: order.get_order_hash(Some(&domain)),
+        signature: None,
+    })
+}
+
+// AuctionSalt panics on values that do not fit into their part of the salt
+fn validate_auction_salt(
+    spec: &OrderSpec,
+    bank_fee: &U256,
+    salt: Option<&U256>,
+) -> Result<(), String> {
+    if salt.is_some_and(|salt| salt_mask().lt(salt)) {
+        return Err("Salt should be less than 18 bytes".to_string());
+    }
+
+    if spec.auction.start_time > u64::from(u32::MAX) {
+        return Err(format!(
+            "Auction start time {} does not fit into uint32",
+            spec.auction.start_time
+        ));
+    }
+
+    if spec.auction.duration >= 1 << 24 {
+        return Err(format!(
+            "Auction duration {} does not fit into uint24",
+            spec.auction.duration
+        ));
+    }
+
+    if spec.auction.initial_rate_bump >= 1 << 24 {
+        return Err(format!(
+            "Initial rate bump {} does not fit into uint24",
+            spec.auction.initial_rate_bump
+        ));
+    }
+
+    if *bank_fee > U256::from(u32::MAX) {
+        return Err(format!("Bank fee {} does not fit into uint32", bank_fee));
+    }
+
+    Ok(())
+}
+
+pub fn sign_order_hash(wallet: &LocalWallet, order_hash: &str) -> Result<Signature, String> {
+    let hash = H256::from_str(order_hash).map_err(|e| format!("Invalid order hash: {}", e))?;
+
+    Ok(wallet.sign_hash(hash))
+}
+
+pub fn render_human(built: &BuiltOrder) -> String {
+    let mut lines = render_order_lines(&built.order);
+
+    lines.extend([
+        "Typed data".to_string(),
+        serde_json::to_string_pretty(&built.typed_data).unwrap(),
+        format!("Order hash: {}", built.order_hash),
+    ]);
+
+    if let Some(signature) = &built.signature {
+        lines.push(format!("Signature: 0x{}", signature));
+    }
+
+    lines.join("\n")
+}
+
+pub fn render_json(built: &BuiltOrder) -> serde_json::Value {
+    json!({
+        "order": built.order.to_json(),
+        "typedData": built.typed_data,
+        "orderHash": built.order_hash,
+        "signature": built.signature.map(|s| format!("0x{}", s)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::{
+        signers::{LocalWallet, Signer},
+        types::{Address, H256, U256},
+    };
+    use foo::{
+        auction_salt::types::AuctionSalt,
+        auction_suffix::types::AuctionSuffix,
+        constants::NetworkEnum,
+        limit_order::LimitOrder,
+        order_classifier::types::{OrderClassifier, OrderKind},
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::{build_order, parse_spec, sign_order_hash};
+
+    const SPEC: &str = r#"
+chain_id = 1
+maker = "0x00000000219ab540356cbb839cbe05303d7705fa"
+maker_asset = "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"
+taker_asset = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+making_amount = "1000000000000000000"
+taking_amount = "1420000000"
+salt = "1000"
+
+[auction]
+start_time = 1673548149
+duration = 180
+initial_rate_bump = 50000
+points = [{ delay = 12, coefficient = 20000 }]
+
+[[whitelist]]
+address = "0x00000000219ab540356cbb839cbe05303d7705fa"
+"#;
+
+    #[test]
+    fn should_build_order_from_toml_spec() {
+        let spec = parse_spec(SPEC, true).unwrap();
+
+        let built = build_order(&spec).unwrap();
+
+        assert_eq!(
+            built.order.salt,
+            U256::from_dec_str(
+                "45118768841948961586167738353692277076075522015101619148498725069326976549864"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            built.order.allowed_sender,
+            Address::from_str("0xa88800cd213da5ae406ce248380802bd53b47647").unwrap()
+        );
+        assert_eq!(
+            format!("{}", built.order.interactions),
+            "0xa88800cd213da5ae406ce248380802bd53b47647\
+            000c004e200000000000000000219ab540356cbb839cbe05303d7705faf486570009"
+        );
+        assert_eq!(
+            built.order_hash,
+            LimitOrder::decode(&built.order).get_order_hash(None)
+        );
+        assert_eq!(
+            AuctionSalt::decode(&built.order.salt).initial_rate_bump,
+            50000
+        );
+        assert_eq!(
+            AuctionSuffix::from_limit_order_v3_struct(&built.order, &built.order.allowed_sender)
+                .unwrap()
+                .suffix
+                .points[0]
+                .delay,
+            12
+        );
+
+        let classification = OrderClassifier::new().classify(&built.order, 1673548100);
+
+        assert_eq!(
+            (classification.kind, classification.network),
+            (OrderKind::Fusion, Some(NetworkEnum::ETHEREUM))
+        );
+    }
+
+    #[test]
+    fn should_build_same_order_from_json_spec() {
+        let spec = parse_spec(SPEC, true).unwrap();
+        let json_spec = r#"{
+            "chain_id": 1,
+            "maker": "0x00000000219ab540356cbb839cbe05303d7705fa",
+            "maker_asset": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "taker_asset": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            "making_amount": "1000000000000000000",
+            "taking_amount": "1420000000",
+            "salt": "1000",
+            "auction": {
+                "start_time": 1673548149,
+                "duration": 180,
+                "initial_rate_bump": 50000,
+                "points": [{ "delay": 12, "coefficient": 20000 }]
+            },
+            "whitelist": [{ "address": "0x00000000219ab540356cbb839cbe05303d7705fa" }]
+        }"#;
+
+        assert_eq!(parse_spec(json_spec, false), Ok(spec));
+    }
+
+    #[test]
+    fn should_refuse_unsupported_chain() {
+        let mut spec = parse_spec(SPEC, true).unwrap();
+        spec.chain_id = 10;
+
+        assert_eq!(
+            build_order(&spec).map(|_| ()),
+            Err("Unsupported chain id 10".to_string())
+        );
+    }
+
+    #[test]
+    fn should_refuse_too_long_salt() {
+        let mut spec = parse_spec(SPEC, true).unwrap();
+        spec.salt = Some((U256::one() << 144).to_string());
+
+        assert_eq!(
+            build_order(&spec).map(|_| ()),
+            Err("Salt should be less than 18 bytes".to_string())
+        );
+    }
+
+    #[test]
+    fn should_refuse_auction_out_of_salt_range() {
+        let mut spec = parse_spec(SPEC, true).unwrap();
+        spec.auction.duration = 1 << 24;
+
+        assert_eq!(
+            build_order(&spec).map(|_| ()),
+            Err("Auction duration 16777216 does not fit into uint24".to_string())
+        );
+
+        let mut spec = parse_spec(SPEC, true).unwrap();
+        spec.auction.initial_rate_bump = 1 << 24;
+
+        assert_eq!(
+            build_order(&spec).map(|_| ()),
+            Err("Initial rate bump 16777216 does not fit into uint24".to_string())
+        );
+
+        let mut spec = parse_spec(SPEC, true).unwrap();
+        spec.auction.start_time = 1 << 32;
+
+        assert_eq!(
+            build_order(&spec).map(|_| ()),
+            Err("Auction start time 4294967296 does not fit into uint32".to_string())
+        );
+    }
+
+    #[test]
+    fn should_sign_order_hash() {
+        let wallet = LocalWallet::from_str(
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+        )
+        .unwrap();
+        let built = build_order(&parse_spec(SPEC, true).unwrap()).unwrap();
+
+        let signature = sign_order_hash(&wallet, &built.order_hash).unwrap();
+
+        assert_eq!(
+            signature
+                .recover(H256::from_str(&built.order_hash).unwrap())
+                .unwrap(),
+            wallet.address()
+        );
+    }
+}
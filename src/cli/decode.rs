@@ -0,0 +1,319 @@
+use clap::Args;
+use ethers::types::{Address, Bytes, U256};
+use foo::{
+    auction_salt::types::AuctionSalt,
+    auction_suffix::types::AuctionSuffix,
+    constants::{get_network_address, NetworkEnum, SETTLEMENT_CONTRACT_ADDRESS_MAP},
+    limit_order::{
+        parser::{parse_interactions, validate_offsets, INTERACTIONS_LAYOUT},
+        types::LimitOrderV3Struct,
+    },
+    utils::trim_0x,
+};
+use serde_json::json;
+
+use super::{read_input, OutputFormat};
+
+#[derive(Args, Debug)]
+pub struct DecodeArgs {
+    /// Order json, abi encoded order hex, path to a file with either of them or "-" for stdin
+    pub input: String,
+
+    /// Chain the order was created for, auction suffix is read from its settlement post interaction
+    #[arg(long, default_value_t = 1)]
+    pub chain_id: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedOrder {
+    pub order: LimitOrderV3Struct,
+    pub interactions: Vec<(String, Bytes)>,
+    pub auction_salt: AuctionSalt,
+    pub auction_suffix: Result<AuctionSuffix, String>,
+}
+
+pub fn run(args: &DecodeArgs, output: OutputFormat) -> Result<String, String> {
+    let order = parse_order(&read_input(&args.input)?)?;
+    let decoded = decode_order(&order, &settlement(args.chain_id)?)?;
+
+    Ok(match output {
+        OutputFormat::Human => render_human(&decoded),
+        OutputFormat::Json => serde_json::to_string_pretty(&render_json(&decoded)).unwrap(),
+    })
+}
+
+pub fn parse_order(input: &str) -> Result<LimitOrderV3Struct, String> {
+    let input = input.trim();
+
+    if input.starts_with('{') {
+        let value: serde_json::Value =
+            serde_json::from_str(input).map_err(|e| format!("Invalid json: {}", e))?;
+
+        // api responses wrap the order into "order" field
+        LimitOrderV3Struct::from_json(value.get("order").unwrap_or(&value))
+    } else {
+        let data = hex::decode(trim_0x(input)).map_err(|e| format!("Invalid hex: {}", e))?;
+
+        LimitOrderV3Struct::decode_abi(&data)
+    }
+}
+
+pub fn settlement(chain_id: u64) -> Result<Address, String> {
+    let network = NetworkEnum::from_chain_id(chain_id)
+        .ok_or_else(|| format!("Unsupported chain id {}", chain_id))?;
+
+    Ok(get_network_address(
+        &SETTLEMENT_CONTRACT_ADDRESS_MAP,
+        network,
+    ))
+}
+
+pub fn decode_order(
+    order: &LimitOrderV3Struct,
+    settlement: &Address,
+) -> Result<DecodedOrder, String> {
+    validate_offsets(&order.offsets, &order.interactions)?;
+
+    let parsed = parse_interactions(&order.offsets, &order.interactions);
+
+    Ok(DecodedOrder {
+        order: order.clone(),
+        interactions: INTERACTIONS_LAYOUT
+            .iter()
+            .map(|name| (name.to_string(), parsed[*name].clone()))
+            .collect(),
+        auction_salt: AuctionSalt::decode(&order.salt),
+        auction_suffix: AuctionSuffix::from_limit_order_v3_struct(order, settlement)
+            .map(|post_interaction| post_interaction.suffix),
+    })
+}
+
+pub fn render_human(decoded: &DecodedOrder) -> String {
+    let salt = &decoded.auction_salt;
+
+    let mut lines = render_order_lines(&decoded.order);
+    lines.push("Interactions".to_string());
+
+    for (name, value) in decoded.interactions.iter() {
+        lines.push(format!("  {:<18} {}", format!("{}:", name), value));
+    }
+
+    lines.extend([
+        "Auction salt".to_string(),
+        format!("  auction_start_time: {}", salt.auction_start_time),
+        format!("  duration:           {}", salt.duration),
+        format!("  initial_rate_bump:  {}", salt.initial_rate_bump),
+        format!("  bank_fee:           {}", salt.bank_fee),
+        format!("  salt:               {}", salt.salt),
+        "Auction suffix".to_string(),
+    ]);
+
+    match &decoded.auction_suffix {
+        Ok(suffix) => {
+            lines.push("  points:".to_string());
+            for point in suffix.points.iter() {
+                lines.push(format!(
+                    "    delay: {}, coefficient: {}",
+                    point.delay, point.coefficient
+                ));
+            }
+
+            lines.push("  whitelist:".to_string());
+            for item in suffix.whitelist.iter() {
+                lines.push(format!(
+                    "    {:?} allowed from {}",
+                    item.address, item.allowance
+                ));
+            }
+
+            lines.extend([
+                format!(
+                    "  public_resolving_deadline: {}",
+                    suffix.public_resolving_deadline
+                ),
+                format!(
+                    "  taker_fee_receiver:        {:?}",
+                    suffix.taker_fee_receiver
+                ),
+                format!("  taker_fee_ratio:           {}", suffix.taker_fee_ratio),
+            ]);
+        }
+        Err(e) => lines.push(format!("  not present: {}", e)),
+    }
+
+    lines.join("\n")
+}
+
+pub fn render_order_lines(order: &LimitOrderV3Struct) -> Vec<String> {
+    vec![
+        "Order".to_string(),
+        format!("  salt:            {}", order.salt),
+        format!("  maker_asset:     {:?}", order.maker_asset),
+        format!("  taker_asset:     {:?}", order.taker_asset),
+        format!("  maker:           {:?}", order.maker),
+        format!("  receiver:        {:?}", order.receiver),
+        format!("  allowed_sender:  {:?}", order.allowed_sender),
+        format!("  making_amount:   {}", order.making_amount),
+        format!("  taking_amount:   {}", order.taking_amount),
+        format!("  offsets:         {}", order.offsets),
+        format!("  interactions:    {}", order.interactions),
+    ]
+}
+
+pub fn render_json(decoded: &DecodedOrder) -> serde_json::Value {
+    let salt = &decoded.auction_salt;
+
+    let interactions: serde_json::Map<String, serde_json::Value> = decoded
+        .interactions
+        .iter()
+        .map(|(name, value)| (name.to_string(), json!(value)))
+        .collect();
+
+    let auction_suffix = match &decoded.auction_suffix {
+        Ok(suffix) => json!({
+            "points": suffix.points.iter().map(|p| json!({
+                "delay": p.delay,
+                "coefficient": p.coefficient,
+            })).collect::<Vec<_>>(),
+            "whitelist": suffix.whitelist.iter().map(|w| json!({
+                "address": w.address,
+                "allowance": w.allowance,
+            })).collect::<Vec<_>>(),
+            "publicResolvingDeadline": number(&suffix.public_resolving_deadline),
+            "takerFeeReceiver": suffix.taker_fee_receiver,
+            "takerFeeRatio": number(&suffix.taker_fee_ratio),
+        }),
+        Err(e) => json!({ "error": e }),
+    };
+
+    json!({
+        "order": decoded.order.to_json(),
+        "interactions": interactions,
+        "auctionSalt": {
+            "auctionStartTime": salt.auction_start_time,
+            "duration": salt.duration,
+            "initialRateBump": salt.initial_rate_bump,
+            "bankFee": number(&salt.bank_fee),
+            "salt": number(&salt.salt),
+        },
+        "auctionSuffix": auction_suffix,
+    })
+}
+
+fn number(value: &U256) -> serde_json::Value {
+    json!(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::types::{Address, Bytes, U256};
+    use foo::limit_order::types::LimitOrderV3Struct;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::{decode_order, parse_order, render_human, render_json, settlement};
+
+    const POST_INTERACTION: &str = "0xa88800cd213da5ae406ce248380802bd53b47647\
+        000c004e200000000000000000219ab540356cbb839cbe05303d7705faf486570009";
+
+    fn fusion_order() -> LimitOrderV3Struct {
+        LimitOrderV3Struct {
+            salt: U256::from_dec_str(
+                "45118768841948961586167738353692277076075522015101619148498725069326976558864",
+            )
+            .unwrap(),
+            maker_asset: Address::from_str("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2").unwrap(),
+            taker_asset: Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap(),
+            maker: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+            receiver: Address::zero(),
+            allowed_sender: settlement(1).unwrap(),
+            making_amount: U256::from(1000000000000000000u64),
+            taking_amount: U256::from(1420000000),
+            offsets: U256::from(54) << 224,
+            interactions: Bytes::from_str(POST_INTERACTION).unwrap(),
+        }
+    }
+
+    #[test]
+    fn should_parse_order_from_json_and_hex() {
+        let order = fusion_order();
+
+        let wrapped = json!({ "order": order.to_json(), "signature": "0x" }).to_string();
+
+        assert_eq!(parse_order(&order.to_json().to_string()), Ok(order.clone()));
+        assert_eq!(parse_order(&wrapped), Ok(order.clone()));
+        assert_eq!(
+            parse_order(&format!("{}\n", order.encode_abi())),
+            Ok(order.clone())
+        );
+        assert!(parse_order("0x1234").is_err());
+    }
+
+    #[test]
+    fn should_decode_fusion_order() {
+        let decoded = decode_order(&fusion_order(), &settlement(1).unwrap()).unwrap();
+
+        assert_eq!(decoded.auction_salt.auction_start_time, 1673548149);
+        assert_eq!(decoded.auction_salt.duration, 180);
+        assert_eq!(decoded.interactions[7].1, fusion_order().interactions);
+
+        let suffix = decoded.auction_suffix.clone().unwrap();
+        assert_eq!(suffix.points[0].coefficient, 20000);
+        assert_eq!(suffix.whitelist.len(), 1);
+
+        let value = render_json(&decoded);
+        assert_eq!(value["auctionSalt"]["initialRateBump"], json!(50000));
+        assert_eq!(
+            value["auctionSuffix"]["whitelist"][0]["address"],
+            json!("0x00000000219ab540356cbb839cbe05303d7705fa")
+        );
+        assert_eq!(
+            value["interactions"]["post_interaction"],
+            json!(POST_INTERACTION)
+        );
+
+        let human = render_human(&decoded);
+        assert!(human.contains("  duration:           180"));
+        assert!(human.contains("    delay: 12, coefficient: 20000"));
+    }
+
+    #[test]
+    fn should_report_missing_suffix_of_plain_order() {
+        let order = LimitOrderV3Struct {
+            offsets: U256::zero(),
+            interactions: Bytes::default(),
+            ..fusion_order()
+        };
+
+        let decoded = decode_order(&order, &settlement(1).unwrap()).unwrap();
+
+        assert_eq!(
+            decoded.auction_suffix,
+            Err("Post interaction is too short".to_string())
+        );
+        assert!(render_human(&decoded).contains("  not present: Post interaction is too short"));
+    }
+
+    #[test]
+    fn should_report_suffix_of_other_chain() {
+        let decoded = decode_order(&fusion_order(), &settlement(137).unwrap()).unwrap();
+
+        assert!(decoded
+            .auction_suffix
+            .unwrap_err()
+            .contains("is not settlement contract"));
+        assert_eq!(settlement(10), Err("Unsupported chain id 10".to_string()));
+    }
+
+    #[test]
+    fn should_refuse_order_with_broken_offsets() {
+        let order = LimitOrderV3Struct {
+            offsets: U256::from(100) << 224,
+            ..fusion_order()
+        };
+
+        assert!(decode_order(&order, &settlement(1).unwrap()).is_err());
+    }
+}
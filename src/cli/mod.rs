@@ -0,0 +1,65 @@
+use std::{fs, io::Read, path::Path};
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+pub mod build;
+pub mod decode;
+pub mod simulate;
+
+#[derive(Parser, Debug)]
+#[command(name = "fusion", about = "Inspect and build 1inch Fusion orders")]
+pub struct Cli {
+    #[arg(long, short, value_enum, global = true, default_value_t = OutputFormat::Human)]
+    pub output: OutputFormat,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Decode an order given as json or as abi encoded hex
+    Decode(decode::DecodeArgs),
+    /// Build fusion order, its typed data and hash from a toml or json spec
+    Build(build::BuildArgs),
+    /// Print auction rate bump and taking amount over the auction duration
+    Simulate(simulate::SimulateArgs),
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Human,
+    Json,
+}
+
+impl Cli {
+    pub fn run(&self) -> Result<(), String> {
+        let output = match &self.command {
+            Command::Decode(args) => decode::run(args, self.output)?,
+            Command::Build(args) => build::run(args, self.output)?,
+            Command::Simulate(args) => simulate::run(args, self.output)?,
+        };
+
+        println!("{}", output);
+
+        Ok(())
+    }
+}
+
+// "-" reads stdin, existing path reads the file, anything else is taken as is
+pub fn read_input(input: &str) -> Result<String, String> {
+    if input == "-" {
+        let mut buf = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buf)
+            .map_err(|e| format!("Cannot read stdin: {}", e))?;
+
+        return Ok(buf);
+    }
+
+    if Path::new(input).is_file() {
+        return fs::read_to_string(input).map_err(|e| format!("Cannot read {}: {}", input, e));
+    }
+
+    Ok(input.to_string())
+}
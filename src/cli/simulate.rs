@@ -0,0 +1,300 @@
+use clap::Args;
+use ethers::types::{Address, U256};
+use foo::{
+    auction_calculator::types::AuctionCalculator, auction_suffix::types::AuctionSuffix,
+    limit_order::types::LimitOrderV3Struct,
+};
+use serde_json::json;
+
+use super::{
+    decode::{parse_order, settlement},
+    read_input, OutputFormat,
+};
+
+const CHART_WIDTH: u64 = 40;
+
+#[derive(Args, Debug)]
+pub struct SimulateArgs {
+    /// Order json, abi encoded order hex, path to a file with either of them or "-" for stdin
+    pub input: String,
+
+    /// Seconds between rows, e.g. 12 to print a row per ethereum block
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub step: u32,
+
+    /// Draw rate bump as a bar next to every row
+    #[arg(long)]
+    pub chart: bool,
+
+    /// Chain the order was created for, auction suffix is read from its settlement post interaction
+    #[arg(long, default_value_t = 1)]
+    pub chain_id: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationRow {
+    pub time: u64,
+    pub rate_bump: u32,
+    pub taking_amount: U256,
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simulation {
+    pub start_time: u64,
+    pub initial_rate_bump: u32,
+    pub rows: Vec<SimulationRow>,
+    // events which happen after the auction is over
+    pub late_events: Vec<(u64, String)>,
+}
+
+pub fn run(args: &SimulateArgs, output: OutputFormat) -> Result<String, String> {
+    let order = parse_order(&read_input(&args.input)?)?;
+    let simulation = simulate(&order, &settlement(args.chain_id)?, args.step)?;
+
+    Ok(match output {
+        OutputFormat::Human => render_human(&simulation, args.chart),
+        OutputFormat::Json => serde_json::to_string_pretty(&render_json(&simulation)).unwrap(),
+    })
+}
+
+pub fn simulate(
+    order: &LimitOrderV3Struct,
+    settlement: &Address,
+    step: u32,
+) -> Result<Simulation, String> {
+    let suffix: AuctionSuffix =
+        AuctionSuffix::from_limit_order_v3_struct(order, settlement)?.suffix;
+    let calculator = AuctionCalculator::from_limit_order_v3_struct(order, settlement)?;
+
+    let start_time = calculator.start_time;
+    let end_time = start_time + calculator.duration as u64;
+
+    let mut events: Vec<(u64, String)> = suffix
+        .whitelist
+        .iter()
+        .map(|item| {
+            (
+                item.allowance as u64,
+                format!("resolver {:?} allowed", item.address),
+            )
+        })
+        .collect();
+    events.push((
+        suffix.public_resolving_deadline.as_u64(),
+        "public resolving deadline".to_string(),
+    ));
+    events.sort_by_key(|(time, _)| *time);
+
+    let mut times: Vec<u64> = (start_time..end_time).step_by(step as usize).collect();
+    times.push(end_time);
+
+    let mut prev_time = None;
+    let rows = times
+        .into_iter()
+        .map(|time| {
+            let rate_bump = calculator.calc_rate_bump(time as u32);
+
+            let row = SimulationRow {
+                time,
+                rate_bump,
+                taking_amount: calculator
+                    .calc_auction_taking_amount(&order.taking_amount, rate_bump),
+                events: events
+                    .iter()
+                    .filter(|(at, _)| *at <= time && prev_time.is_none_or(|prev| *at > prev))
+                    .map(|(_, event)| event.to_string())
+                    .collect(),
+            };
+            prev_time = Some(time);
+
+            row
+        })
+        .collect();
+
+    Ok(Simulation {
+        start_time,
+        initial_rate_bump: calculator.initial_rate_bump,
+        rows,
+        late_events: events
+            .into_iter()
+            .filter(|(at, _)| *at > end_time)
+            .collect(),
+    })
+}
+
+pub fn render_human(simulation: &Simulation, chart: bool) -> String {
+    let mut lines = vec![format!(
+        "{:>12} {:>8} {:>10} {:>30}{}  events",
+        "time",
+        "offset",
+        "rate_bump",
+        "taking_amount",
+        if chart {
+            format!("  {:<width$}", "", width = CHART_WIDTH as usize)
+        } else {
+            "".to_string()
+        }
+    )];
+
+    for row in simulation.rows.iter() {
+        let bar = if chart {
+            let width = if simulation.initial_rate_bump == 0 {
+                0
+            } else {
+                row.rate_bump as u64 * CHART_WIDTH / simulation.initial_rate_bump as u64
+            };
+
+            format!(
+                "  {:<width$}",
+                "#".repeat(width as usize),
+                width = CHART_WIDTH as usize
+            )
+        } else {
+            "".to_string()
+        };
+
+        lines.push(
+            format!(
+                "{:>12} {:>8} {:>10} {:>30}{}  {}",
+                row.time,
+                format!("+{}s", row.time - simulation.start_time),
+                row.rate_bump,
+                row.taking_amount,
+                bar,
+                row.events.join(", ")
+            )
+            .trim_end()
+            .to_string(),
+        );
+    }
+
+    for (time, event) in simulation.late_events.iter() {
+        lines.push(format!("after auction end at {}: {}", time, event));
+    }
+
+    lines.join("\n")
+}
+
+pub fn render_json(simulation: &Simulation) -> serde_json::Value {
+    json!({
+        "startTime": simulation.start_time,
+        "rows": simulation.rows.iter().map(|row| json!({
+            "time": row.time,
+            "rateBump": row.rate_bump,
+            "takingAmount": row.taking_amount.to_string(),
+            "events": row.events,
+        })).collect::<Vec<_>>(),
+        "lateEvents": simulation.late_events.iter().map(|(time, event)| json!({
+            "time": time,
+            "event": event,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::types::{Address, Bytes, U256};
+    use foo::limit_order::types::LimitOrderV3Struct;
+    use pretty_assertions::assert_eq;
+
+    use crate::cli::decode::settlement;
+
+    use super::{render_human, render_json, simulate};
+
+    // auction starts at 1673548149 and lasts 180 seconds,
+    // resolver is allowed at 1673548200 and public resolving deadline is 1673548260
+    fn fusion_order() -> LimitOrderV3Struct {
+        LimitOrderV3Struct {
+            salt: U256::from_dec_str(
+                "45118768841948961586167738353692277076075522015101619148498725069326976558864",
+            )
+            .unwrap(),
+            maker_asset: Address::from_str("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2").unwrap(),
+            taker_asset: Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap(),
+            maker: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+            receiver: Address::zero(),
+            allowed_sender: settlement(1).unwrap(),
+            making_amount: U256::from(1000000000000000000u64),
+            taking_amount: U256::from(1420000000),
+            offsets: U256::from(54) << 224,
+            interactions: Bytes::from_str(
+                "0xa88800cd213da5ae406ce248380802bd53b47647\
+                000c004e2063c051a800000000219ab540356cbb839cbe05303d7705fa63c051e409",
+            )
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn should_simulate_auction() {
+        let simulation = simulate(&fusion_order(), &settlement(1).unwrap(), 12).unwrap();
+
+        assert_eq!(simulation.rows.len(), 16);
+        assert_eq!(simulation.rows[0].time, 1673548149);
+        assert_eq!(simulation.rows[0].rate_bump, 50000);
+        assert_eq!(simulation.rows[0].taking_amount, U256::from(1427100000));
+        assert_eq!(simulation.rows[5].time, 1673548209);
+        assert_eq!(simulation.rows[5].rate_bump, 14285);
+        assert_eq!(simulation.rows[5].taking_amount, U256::from(1422028470));
+        assert_eq!(simulation.rows[15].time, 1673548329);
+        assert_eq!(simulation.rows[15].rate_bump, 0);
+        assert_eq!(simulation.rows[15].taking_amount, U256::from(1420000000));
+    }
+
+    #[test]
+    fn should_mark_resolver_allowance_and_deadline() {
+        let simulation = simulate(&fusion_order(), &settlement(1).unwrap(), 12).unwrap();
+
+        let marked: Vec<(u64, Vec<String>)> = simulation
+            .rows
+            .iter()
+            .filter(|row| !row.events.is_empty())
+            .map(|row| (row.time, row.events.clone()))
+            .collect();
+
+        assert_eq!(
+            marked,
+            vec![
+                (
+                    1673548209,
+                    vec!["resolver 0x00000000219ab540356cbb839cbe05303d7705fa allowed".to_string()]
+                ),
+                (1673548269, vec!["public resolving deadline".to_string()]),
+            ]
+        );
+        assert_eq!(simulation.late_events, vec![]);
+    }
+
+    #[test]
+    fn should_render_simulation() {
+        let simulation = simulate(&fusion_order(), &settlement(1).unwrap(), 60).unwrap();
+
+        let human = render_human(&simulation, true);
+        let lines: Vec<&str> = human.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].contains(&"#".repeat(40)));
+        assert!(lines[4].ends_with(&format!("0 {:>30}", 1420000000)));
+
+        let value = render_json(&simulation);
+        assert_eq!(value["rows"][0]["rateBump"], 50000);
+        assert_eq!(value["rows"][0]["takingAmount"], "1427100000");
+    }
+
+    #[test]
+    fn should_refuse_order_without_suffix() {
+        let order = LimitOrderV3Struct {
+            offsets: U256::zero(),
+            interactions: Bytes::default(),
+            ..fusion_order()
+        };
+
+        assert_eq!(
+            simulate(&order, &settlement(1).unwrap(), 1).map(|_| ()),
+            Err("Post interaction is too short".to_string())
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use ethers::types::{Address, U256};
 
 pub const NATIVE_CURRENCY: &str = "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee";
@@ -8,13 +10,33 @@ pub fn zero_number() -> U256 {
 
 pub const ZX: &str = "0x";
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum NetworkEnum {
     ETHEREUM = 1,
     POLYGON = 137,
     BINANCE = 56,
 }
 
+impl NetworkEnum {
+    pub fn from_chain_id(chain_id: u64) -> Option<Self> {
+        match chain_id {
+            1 => Some(NetworkEnum::ETHEREUM),
+            137 => Some(NetworkEnum::POLYGON),
+            56 => Some(NetworkEnum::BINANCE),
+            _ => None,
+        }
+    }
+}
+
+pub fn get_network_address(map: &[(NetworkEnum, &str)], network: NetworkEnum) -> Address {
+    let (_, address) = map
+        .iter()
+        .find(|(n, _)| *n == network)
+        .unwrap_or_else(|| panic!("No address for network {:?}", network));
+
+    Address::from_str(address).unwrap()
+}
+
 pub const WRAPPER_ADDRESS_MAP: [(NetworkEnum, &str); 3] = [
     (
         NetworkEnum::ETHEREUM,
@@ -61,3 +83,11 @@ pub const SETTLEMENT_CONTRACT_ADDRESS_MAP: [(NetworkEnum, &str); 3] = [
 ];
 
 pub const ONE_INCH_ROUTER_V5: &str = "0x1111111254eeb25477b68fb85ed929f73a960582";
+
+pub const ONE_INCH_ROUTER_V6: &str = "0x111111125421ca6dc452d289314280a0f8842a65";
+
+// Uniswap Permit2, same address on every network
+pub const PERMIT2: &str = "0x000000000022d473030f116ddee9f6b43ac78ba3";
+
+// Multicall3, same address on every network
+pub const MULTICALL3: &str = "0xca11bde05977b3631167028862be2a173976ca11";
@@ -0,0 +1,11 @@
+// https://github.com/1inch/cross-chain-swap/blob/master/contracts/libraries/TimelocksLib.sol
+pub const TIMELOCK_STAGE_BITS: usize = 32;
+pub const DEPLOYED_AT_OFFSET: usize = 224;
+
+// https://github.com/1inch/cross-chain-swap/blob/master/contracts/EscrowFactory.sol
+pub const ESCROW_EXTRA_DATA_LENGTH: usize = 160; // hashlock, dstChainId, dstToken, deposits, timelocks
+
+pub const SAFETY_DEPOSIT_DST_BITS: usize = 128;
+
+pub const PARTS_COUNT_OFFSET: usize = 240; // parts count in the highest 16 bits of multiple fills hashlock
+pub const PARTS_COUNT_BITS: usize = 16;
@@ -0,0 +1,353 @@
+use ethers::types::{Address, Bytes, H256, U256};
+
+use crate::{
+    auction_suffix::types::{AuctionSuffix, PostInteractionData},
+    constants::ZERO_ADDRESS,
+    limit_order::LimitOrder,
+    utils::Maskn,
+};
+
+use super::{
+    constants::{ESCROW_EXTRA_DATA_LENGTH, SAFETY_DEPOSIT_DST_BITS},
+    hash_lock::HashLock,
+    immutables::Immutables,
+    timelocks::Timelocks,
+};
+
+// post interaction is settlement address | escrow data | user interaction | auction suffix,
+// the suffix stays at the tail where the settlement parser expects it and
+// the fixed length escrow data is skipped at the front of the interaction part
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscrowExtension {
+    pub auction_suffix: AuctionSuffix,
+    pub hash_lock: HashLock,
+    pub dst_chain_id: U256,
+    pub dst_token: Address,
+    pub src_safety_deposit: U256,
+    pub dst_safety_deposit: U256,
+    pub timelocks: Timelocks,
+}
+
+impl EscrowExtension {
+    pub fn decode(post_interaction: &Bytes, settlement: &Address) -> Result<Self, String> {
+        let data = AuctionSuffix::from_post_interaction(post_interaction, settlement)?;
+
+        if data.interaction.len() < ESCROW_EXTRA_DATA_LENGTH {
+            return Err("Wrong escrow extension length".to_string());
+        }
+
+        let extra_data = &data.interaction[..ESCROW_EXTRA_DATA_LENGTH];
+        let word = |index: usize| &extra_data[index * 32..(index + 1) * 32];
+
+        let safety_deposit = U256::from(word(3));
+
+        Ok(Self {
+            auction_suffix: data.suffix,
+            hash_lock: HashLock::decode(&H256::from_slice(word(0))),
+            dst_chain_id: U256::from(word(1)),
+            dst_token: Address::from_slice(&word(2)[12..]),
+            src_safety_deposit: safety_deposit >> SAFETY_DEPOSIT_DST_BITS,
+            dst_safety_deposit: safety_deposit.maskn(SAFETY_DEPOSIT_DST_BITS),
+            timelocks: Timelocks::decode(&U256::from(word(4))),
+        })
+    }
+
+    pub fn from_limit_order(order: &LimitOrder, settlement: &Address) -> Result<Self, String> {
+        Self::decode(order.post_interaction(), settlement)
+    }
+
+    pub fn build_extra_data(&self) -> Bytes {
+        if self.src_safety_deposit.bits() > SAFETY_DEPOSIT_DST_BITS
+            || self.dst_safety_deposit.bits() > SAFETY_DEPOSIT_DST_BITS
+        {
+            panic!("safety deposit should be less than 2^128");
+        }
+
+        let words = [
+            U256::from(self.hash_lock.build().as_bytes()),
+            self.dst_chain_id,
+            U256::from(self.dst_token.as_bytes()),
+            (self.src_safety_deposit << SAFETY_DEPOSIT_DST_BITS) | self.dst_safety_deposit,
+            self.timelocks.build(),
+        ];
+
+        words
+            .iter()
+            .flat_map(|word| {
+                let mut bytes = [0u8; 32];
+                word.to_big_endian(&mut bytes);
+                bytes
+            })
+            .collect::<Vec<u8>>()
+            .into()
+    }
+
+    pub fn build_post_interaction(&self, interaction: &Bytes, settlement: &Address) -> Bytes {
+        PostInteractionData {
+            interaction: [self.build_extra_data().to_vec(), interaction.to_vec()]
+                .concat()
+                .into(),
+            suffix: self.auction_suffix.clone(),
+        }
+        .build(settlement)
+    }
+
+    pub fn apply(
+        &self,
+        order: &LimitOrder,
+        interaction: &Bytes,
+        settlement: &Address,
+    ) -> LimitOrder {
+        order.with_post_interaction(self.build_post_interaction(interaction, settlement))
+    }
+
+    pub fn get_src_immutables(
+        &self,
+        order_hash: &H256,
+        order: &LimitOrder,
+        taker: &Address,
+        amount: &U256,
+        deployed_at: u32,
+    ) -> Immutables {
+        Immutables {
+            order_hash: *order_hash,
+            hash_lock: self.hash_lock.build(),
+            maker: order.maker(),
+            taker: *taker,
+            token: order.maker_asset(),
+            amount: *amount,
+            safety_deposit: self.src_safety_deposit,
+            timelocks: self.timelocks.with_deployed_at(deployed_at).build(),
+        }
+    }
+
+    // on destination chain the maker receives funds, the resolver is the taker
+    pub fn get_dst_immutables(
+        &self,
+        order_hash: &H256,
+        order: &LimitOrder,
+        taker: &Address,
+        amount: &U256,
+        deployed_at: u32,
+    ) -> Immutables {
+        let maker = if order.receiver() == ZERO_ADDRESS {
+            order.maker()
+        } else {
+            order.receiver()
+        };
+
+        Immutables {
+            order_hash: *order_hash,
+            hash_lock: self.hash_lock.build(),
+            maker,
+            taker: *taker,
+            token: self.dst_token,
+            amount: *amount,
+            safety_deposit: self.dst_safety_deposit,
+            timelocks: self.timelocks.with_deployed_at(deployed_at).build(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::{
+        abi::{encode, Token},
+        types::{Address, Bytes, H256, U256},
+        utils::keccak256,
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        auction_suffix::{
+            parser::types::{AuctionPoint, AuctionWhitelistItem},
+            types::{AuctionSuffix, PostInteractionData, SettlementSuffixData},
+        },
+        cross_chain::{
+            hash_lock::HashLock,
+            timelocks::{Timelocks, TimelocksData},
+        },
+        limit_order::{LimitOrder, OrderInfoData},
+        salt::RandomSalt,
+        test_utils::settlement,
+    };
+
+    use super::EscrowExtension;
+
+    fn escrow_extension() -> EscrowExtension {
+        EscrowExtension {
+            auction_suffix: AuctionSuffix::new(SettlementSuffixData {
+                points: vec![AuctionPoint {
+                    coefficient: 20000,
+                    delay: 12,
+                }],
+                whitelist: vec![AuctionWhitelistItem {
+                    address: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa")
+                        .unwrap(),
+                    allowance: 0,
+                }],
+                public_resolving_deadline: Some(1673549418),
+                fee: None,
+            }),
+            hash_lock: HashLock::for_single_fill(&H256::from_low_u64_be(1)),
+            dst_chain_id: U256::from(137),
+            dst_token: Address::from_str("0x2791bca1f2de4661ed88a30c99a7a9449aa84174").unwrap(),
+            src_safety_deposit: U256::from(1000),
+            dst_safety_deposit: U256::from(2000),
+            timelocks: Timelocks::new(TimelocksData {
+                src_withdrawal: 10,
+                src_public_withdrawal: 120,
+                src_cancellation: 121,
+                src_public_cancellation: 122,
+                dst_withdrawal: 10,
+                dst_public_withdrawal: 100,
+                dst_cancellation: 101,
+            })
+            .unwrap(),
+        }
+    }
+
+    fn limit_order() -> LimitOrder {
+        LimitOrder::new(
+            &OrderInfoData {
+                maker_asset: Address::from_str("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")
+                    .unwrap(),
+                taker_asset: Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")
+                    .unwrap(),
+                making_amount: U256::from(1000000000000000000u64),
+                taking_amount: U256::from(1420000000),
+                maker: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+                salt: Some(U256::from(1673549418040u64)),
+                allowed_sender: None,
+                receiver: None,
+            },
+            None,
+            &RandomSalt,
+        )
+    }
+
+    #[test]
+    fn should_encode_and_decode_escrow_extension() {
+        let extension = escrow_extension();
+        let interaction = Bytes::from_str("0x08b067ad41e45babe5bbb52fc2fe7f692f628b06").unwrap();
+
+        let post_interaction = extension.build_post_interaction(&interaction, &settlement());
+
+        assert_eq!(extension.build_extra_data().len(), 160);
+        assert_eq!(&post_interaction[..20], settlement().as_bytes());
+        assert_eq!(
+            post_interaction[20..180].to_vec(),
+            extension.build_extra_data().to_vec()
+        );
+        assert_eq!(post_interaction[180..200].to_vec(), interaction.to_vec());
+        assert_eq!(
+            EscrowExtension::decode(&post_interaction, &settlement()),
+            Ok(extension)
+        );
+    }
+
+    #[test]
+    fn should_refuse_post_interaction_without_escrow_data() {
+        let extension = escrow_extension();
+        let post_interaction = PostInteractionData {
+            interaction: Bytes::from(vec![0; 159]),
+            suffix: extension.auction_suffix.clone(),
+        }
+        .build(&settlement());
+
+        assert_eq!(
+            EscrowExtension::decode(&post_interaction, &settlement()),
+            Err("Wrong escrow extension length".to_string())
+        );
+        assert!(EscrowExtension::decode(
+            &extension.build_post_interaction(&Bytes::default(), &settlement()),
+            &Address::from_low_u64_be(0xbad)
+        )
+        .unwrap_err()
+        .contains("is not settlement contract"));
+    }
+
+    #[test]
+    fn should_apply_escrow_extension_to_limit_order() {
+        let extension = escrow_extension();
+
+        let interaction = Bytes::from_str("0x08b067ad41e45babe5bbb52fc2fe7f692f628b06").unwrap();
+
+        let order = extension.apply(&limit_order(), &interaction, &settlement());
+
+        assert_eq!(
+            EscrowExtension::from_limit_order(&order, &settlement()),
+            Ok(extension.clone())
+        );
+        assert_eq!(
+            LimitOrder::decode(&order.build()).post_interaction(),
+            order.post_interaction()
+        );
+
+        // auction suffix is found by the settlement parser
+        let post_interaction =
+            AuctionSuffix::from_limit_order_v3_struct(&order.build(), &settlement()).unwrap();
+
+        assert_eq!(post_interaction.suffix, extension.auction_suffix);
+        assert_eq!(
+            post_interaction.interaction,
+            Bytes::from([extension.build_extra_data().to_vec(), interaction.to_vec()].concat())
+        );
+    }
+
+    #[test]
+    fn should_hash_src_immutables() {
+        let extension = escrow_extension();
+        let order = limit_order();
+        let order_hash = H256::from_str(&order.get_order_hash(None)).unwrap();
+        let taker = Address::from_str("0x1d0ae300eec4093cee4367c00b228d10a5c7ac63").unwrap();
+
+        let immutables = extension.get_src_immutables(
+            &order_hash,
+            &order,
+            &taker,
+            &U256::from(1000000000000000000u64),
+            1673548149,
+        );
+
+        let expected = keccak256(encode(&[
+            Token::FixedBytes(order_hash.as_bytes().to_vec()),
+            Token::FixedBytes(extension.hash_lock.build().as_bytes().to_vec()),
+            Token::Address(order.maker()),
+            Token::Address(taker),
+            Token::Address(order.maker_asset()),
+            Token::Uint(U256::from(1000000000000000000u64)),
+            Token::Uint(U256::from(1000)),
+            Token::Uint(
+                U256::from_str(
+                    "0x63c0517500000065000000640000000a0000007a00000079000000780000000a",
+                )
+                .unwrap(),
+            ),
+        ]));
+
+        assert_eq!(immutables.build().len(), 256);
+        assert_eq!(immutables.hash(), H256::from(expected));
+    }
+
+    #[test]
+    fn should_use_receiver_as_maker_of_dst_immutables() {
+        let extension = escrow_extension();
+        let receiver = Address::from_str("0x0000000000000000000000000000000000000001").unwrap();
+        let order = limit_order().with_receiver(receiver);
+
+        let immutables = extension.get_dst_immutables(
+            &H256::zero(),
+            &order,
+            &Address::zero(),
+            &U256::from(1420000000),
+            1673548149,
+        );
+
+        assert_eq!(immutables.maker, receiver);
+        assert_eq!(immutables.token, extension.dst_token);
+        assert_eq!(immutables.safety_deposit, U256::from(2000));
+    }
+}
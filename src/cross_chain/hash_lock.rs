@@ -0,0 +1,128 @@
+use ethers::{
+    types::{H256, U256},
+    utils::keccak256,
+};
+
+use crate::utils::Maskn;
+
+use super::{
+    constants::{PARTS_COUNT_BITS, PARTS_COUNT_OFFSET},
+    merkle::MerkleTree,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashLock {
+    value: H256,
+}
+
+impl HashLock {
+    pub fn hash_secret(secret: &H256) -> H256 {
+        H256::from(keccak256(secret.as_bytes()))
+    }
+
+    // leaf of multiple fills merkle tree: keccak256(abi.encodePacked(uint64 idx, bytes32 secretHash))
+    pub fn get_merkle_leaves(secret_hashes: &[H256]) -> Vec<H256> {
+        secret_hashes
+            .iter()
+            .enumerate()
+            .map(|(idx, secret_hash)| {
+                let packed = [
+                    (idx as u64).to_be_bytes().as_slice(),
+                    secret_hash.as_bytes(),
+                ]
+                .concat();
+
+                H256::from(keccak256(packed))
+            })
+            .collect()
+    }
+
+    pub fn for_single_fill(secret: &H256) -> Self {
+        Self {
+            value: Self::hash_secret(secret),
+        }
+    }
+
+    pub fn for_multiple_fills(leaves: &[H256]) -> Self {
+        if leaves.len() <= 2 {
+            panic!("leaves array must be greater than 2, use single fill hashlock instead");
+        }
+
+        let root = U256::from(MerkleTree::new(leaves).root().as_bytes());
+        let parts_count = U256::from(leaves.len() - 1) << PARTS_COUNT_OFFSET;
+
+        let mut value = [0u8; 32];
+        (root.maskn(PARTS_COUNT_OFFSET) | parts_count).to_big_endian(&mut value);
+
+        Self {
+            value: H256::from(value),
+        }
+    }
+
+    pub fn decode(value: &H256) -> Self {
+        Self { value: *value }
+    }
+
+    pub fn build(&self) -> H256 {
+        self.value
+    }
+
+    // only meaningful for multiple fills hashlock
+    pub fn get_parts_count(&self) -> u16 {
+        (U256::from(self.value.as_bytes()) >> PARTS_COUNT_OFFSET)
+            .maskn(PARTS_COUNT_BITS)
+            .as_u32() as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::{types::H256, utils::keccak256};
+    use pretty_assertions::assert_eq;
+
+    use super::HashLock;
+
+    #[test]
+    fn should_create_single_fill_hash_lock() {
+        let secret =
+            H256::from_str("0x531d1e7aae0f5ad4f1ff8a7a7e3c3ab7fb1e3e7ee4d2f6a7c27c5b6e1b2c9d0e")
+                .unwrap();
+
+        let hash_lock = HashLock::for_single_fill(&secret);
+
+        assert_eq!(hash_lock.build(), H256::from(keccak256(secret.as_bytes())));
+    }
+
+    #[test]
+    fn should_create_multiple_fills_hash_lock() {
+        let secrets: Vec<H256> = (1..=4u64).map(H256::from_low_u64_be).collect();
+        let secret_hashes: Vec<H256> = secrets.iter().map(HashLock::hash_secret).collect();
+        let leaves = HashLock::get_merkle_leaves(&secret_hashes);
+
+        let hash_lock = HashLock::for_multiple_fills(&leaves);
+
+        assert_eq!(hash_lock.get_parts_count(), 3);
+        assert_eq!(HashLock::decode(&hash_lock.build()), hash_lock);
+    }
+
+    #[test]
+    fn should_encode_merkle_leaf_as_packed_index_and_hash() {
+        let secret_hash = H256::from_low_u64_be(42);
+
+        let leaves = HashLock::get_merkle_leaves(&[secret_hash, secret_hash]);
+
+        let mut packed = vec![0u8; 7];
+        packed.push(1);
+        packed.extend_from_slice(secret_hash.as_bytes());
+
+        assert_eq!(leaves[1], H256::from(keccak256(packed)));
+    }
+
+    #[test]
+    #[should_panic(expected = "leaves array must be greater than 2")]
+    fn should_fail_to_create_multiple_fills_hash_lock_for_two_leaves() {
+        HashLock::for_multiple_fills(&[H256::zero(), H256::zero()]);
+    }
+}
@@ -0,0 +1,38 @@
+use ethers::{
+    abi::{encode, Token},
+    types::{Address, Bytes, H256, U256},
+    utils::keccak256,
+};
+
+// https://github.com/1inch/cross-chain-swap/blob/master/contracts/interfaces/IBaseEscrow.sol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Immutables {
+    pub order_hash: H256,
+    pub hash_lock: H256,
+    pub maker: Address,
+    pub taker: Address,
+    pub token: Address,
+    pub amount: U256,
+    pub safety_deposit: U256,
+    pub timelocks: U256,
+}
+
+impl Immutables {
+    pub fn build(&self) -> Bytes {
+        encode(&[
+            Token::FixedBytes(self.order_hash.as_bytes().to_vec()),
+            Token::FixedBytes(self.hash_lock.as_bytes().to_vec()),
+            Token::Address(self.maker),
+            Token::Address(self.taker),
+            Token::Address(self.token),
+            Token::Uint(self.amount),
+            Token::Uint(self.safety_deposit),
+            Token::Uint(self.timelocks),
+        ])
+        .into()
+    }
+
+    pub fn hash(&self) -> H256 {
+        H256::from(keccak256(self.build()))
+    }
+}
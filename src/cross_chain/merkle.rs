@@ -0,0 +1,193 @@
+use ethers::{types::H256, utils::keccak256};
+
+// https://github.com/OpenZeppelin/merkle-tree SimpleMerkleTree with sorted leaves and pairs,
+// the same layout is used by the cross chain sdk to build hashlock of multiple fills orders
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerkleTree {
+    tree: Vec<H256>,
+    // position in tree of leaf passed with the given index
+    tree_indices: Vec<usize>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: &[H256]) -> Self {
+        if leaves.is_empty() {
+            panic!("Expected non-zero number of leaves");
+        }
+
+        let mut sorted: Vec<(usize, H256)> = leaves.iter().copied().enumerate().collect();
+        sorted.sort_by_key(|(_, leaf)| *leaf);
+
+        let tree_len = 2 * leaves.len() - 1;
+        let mut tree = vec![H256::zero(); tree_len];
+        let mut tree_indices = vec![0; leaves.len()];
+
+        for (i, (leaf_index, leaf)) in sorted.iter().enumerate() {
+            tree[tree_len - 1 - i] = *leaf;
+            tree_indices[*leaf_index] = tree_len - 1 - i;
+        }
+
+        for i in (0..tree_len - leaves.len()).rev() {
+            tree[i] = hash_pair(&tree[2 * i + 1], &tree[2 * i + 2]);
+        }
+
+        Self { tree, tree_indices }
+    }
+
+    pub fn root(&self) -> H256 {
+        self.tree[0]
+    }
+
+    pub fn get_proof(&self, index: usize) -> Vec<H256> {
+        let mut tree_index = *self
+            .tree_indices
+            .get(index)
+            .unwrap_or_else(|| panic!("Leaf index {} is out of bounds", index));
+
+        let mut proof = vec![];
+
+        while tree_index > 0 {
+            // usize::is_multiple_of needs rust 1.87
+            #[allow(clippy::manual_is_multiple_of)]
+            let sibling_index = if tree_index % 2 == 0 {
+                tree_index - 1
+            } else {
+                tree_index + 1
+            };
+
+            proof.push(self.tree[sibling_index]);
+            tree_index = (tree_index - 1) / 2;
+        }
+
+        proof
+    }
+
+    pub fn verify(root: &H256, leaf: &H256, proof: &[H256]) -> bool {
+        Self::process_proof(leaf, proof) == *root
+    }
+
+    pub fn process_proof(leaf: &H256, proof: &[H256]) -> H256 {
+        proof
+            .iter()
+            .fold(*leaf, |computed, sibling| hash_pair(&computed, sibling))
+    }
+}
+
+pub fn hash_pair(a: &H256, b: &H256) -> H256 {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+
+    H256::from(keccak256([left.as_bytes(), right.as_bytes()].concat()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::{
+        abi::{encode, Token},
+        types::{Address, H256, U256},
+        utils::keccak256,
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::{hash_pair, MerkleTree};
+
+    fn leaves(count: u64) -> Vec<H256> {
+        (1..=count)
+            .map(|i| H256::from_low_u64_be(i * 7919 % 101))
+            .collect()
+    }
+
+    #[test]
+    fn should_use_leaf_as_root_of_single_leaf_tree() {
+        let leaf = H256::from_low_u64_be(1);
+
+        let tree = MerkleTree::new(&[leaf]);
+
+        assert_eq!(tree.root(), leaf);
+        assert_eq!(tree.get_proof(0), vec![]);
+    }
+
+    #[test]
+    fn should_hash_sorted_pair() {
+        let a = H256::from_low_u64_be(2);
+        let b = H256::from_low_u64_be(1);
+
+        assert_eq!(MerkleTree::new(&[a, b]).root(), hash_pair(&b, &a));
+        assert_eq!(hash_pair(&a, &b), hash_pair(&b, &a));
+    }
+
+    #[test]
+    fn should_verify_proofs_of_all_leaves() {
+        for count in 2..=9 {
+            let leaves = leaves(count);
+            let tree = MerkleTree::new(&leaves);
+
+            for (index, leaf) in leaves.iter().enumerate() {
+                assert!(
+                    MerkleTree::verify(&tree.root(), leaf, &tree.get_proof(index)),
+                    "proof of leaf {} in tree of {} leaves",
+                    index,
+                    count
+                );
+            }
+        }
+    }
+
+    // example of https://github.com/OpenZeppelin/merkle-tree README, StandardMerkleTree
+    // has the same layout, its leaves are double hashed abi encoded values
+    #[test]
+    fn should_match_openzeppelin_merkle_tree() {
+        let leaf = |address: &str, amount: &str| {
+            let encoded = encode(&[
+                Token::Address(Address::from_str(address).unwrap()),
+                Token::Uint(U256::from_dec_str(amount).unwrap()),
+            ]);
+
+            H256::from(keccak256(keccak256(encoded)))
+        };
+        let leaves = [
+            leaf(
+                "0x1111111111111111111111111111111111111111",
+                "5000000000000000000",
+            ),
+            leaf(
+                "0x2222222222222222222222222222222222222222",
+                "2500000000000000000",
+            ),
+        ];
+
+        let tree = MerkleTree::new(&leaves);
+
+        assert_eq!(
+            tree.root(),
+            H256::from_str("0xd4dee0beab2d53f2cc83e567171bd2820e49898130a22622b10ead383e90bd77")
+                .unwrap()
+        );
+        assert_eq!(
+            tree.get_proof(0),
+            vec![H256::from_str(
+                "0xb92c48e9d7abe27fd8dfd6b5dfdbfb1c9a463f80c712b66f3a5180a090cccafc"
+            )
+            .unwrap()]
+        );
+    }
+
+    #[test]
+    fn should_not_verify_proof_of_other_leaf() {
+        let leaves = leaves(5);
+        let tree = MerkleTree::new(&leaves);
+
+        assert!(!MerkleTree::verify(
+            &tree.root(),
+            &leaves[0],
+            &tree.get_proof(1)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "Leaf index 5 is out of bounds")]
+    fn should_fail_to_get_proof_out_of_bounds() {
+        MerkleTree::new(&leaves(5)).get_proof(5);
+    }
+}
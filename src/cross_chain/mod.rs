@@ -0,0 +1,7 @@
+pub mod constants;
+pub mod escrow_extension;
+pub mod hash_lock;
+pub mod immutables;
+pub mod merkle;
+pub mod secrets;
+pub mod timelocks;
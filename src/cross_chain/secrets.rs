@@ -0,0 +1,163 @@
+use ethers::types::{H256, U256};
+use rand::RngCore;
+
+use super::{hash_lock::HashLock, merkle::MerkleTree};
+
+pub fn generate_secret() -> H256 {
+    let mut secret = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut secret);
+
+    H256::from(secret)
+}
+
+pub fn generate_secrets(count: usize) -> Vec<H256> {
+    (0..count).map(|_| generate_secret()).collect()
+}
+
+pub fn hash_secrets(secrets: &[H256]) -> Vec<H256> {
+    secrets.iter().map(HashLock::hash_secret).collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecretsTree {
+    pub secret_hashes: Vec<H256>,
+    pub leaves: Vec<H256>,
+    pub tree: MerkleTree,
+}
+
+impl SecretsTree {
+    pub fn new(secret_hashes: &[H256]) -> Self {
+        let leaves = HashLock::get_merkle_leaves(secret_hashes);
+
+        Self {
+            secret_hashes: secret_hashes.to_vec(),
+            tree: MerkleTree::new(&leaves),
+            leaves,
+        }
+    }
+
+    pub fn from_secrets(secrets: &[H256]) -> Self {
+        Self::new(&hash_secrets(secrets))
+    }
+
+    pub fn hash_lock(&self) -> HashLock {
+        HashLock::for_multiple_fills(&self.leaves)
+    }
+
+    // proof which resolver passes together with secret hash of given index
+    pub fn get_proof(&self, index: usize) -> Vec<H256> {
+        self.tree.get_proof(index)
+    }
+}
+
+// https://github.com/1inch/cross-chain-swap/blob/master/contracts/EscrowFactory.sol _isValidPartialFill
+// N + 1 secrets are needed for order split into N parts, the last one is used only to fill the order completely
+pub fn get_multiple_fill_idx(
+    making_amount: &U256,
+    fill_amount: &U256,
+    remaining_amount: &U256,
+    parts_count: u16,
+) -> Result<usize, String> {
+    if fill_amount.is_zero() || fill_amount > remaining_amount || remaining_amount > making_amount {
+        return Err("Invalid fill amount".to_string());
+    }
+
+    let calculated_idx = U256::from(parts_count)
+        .checked_mul(making_amount - remaining_amount + fill_amount - 1)
+        .ok_or_else(|| "Fill amount overflow".to_string())?
+        / making_amount;
+
+    if remaining_amount == fill_amount {
+        Ok((calculated_idx + 1).as_usize())
+    } else {
+        Ok(calculated_idx.as_usize())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::U256;
+    use pretty_assertions::assert_eq;
+
+    use crate::cross_chain::merkle::MerkleTree;
+
+    use super::{generate_secrets, get_multiple_fill_idx, SecretsTree};
+
+    #[test]
+    fn should_generate_unique_secrets() {
+        let secrets = generate_secrets(11);
+
+        let mut unique = secrets.clone();
+        unique.sort();
+        unique.dedup();
+
+        assert_eq!(unique.len(), 11);
+    }
+
+    #[test]
+    fn should_build_secrets_tree_with_verifiable_proofs() {
+        let secrets = generate_secrets(5);
+        let tree = SecretsTree::from_secrets(&secrets);
+
+        assert_eq!(tree.hash_lock().get_parts_count(), 4);
+
+        for (index, leaf) in tree.leaves.iter().enumerate() {
+            assert!(MerkleTree::verify(
+                &tree.tree.root(),
+                leaf,
+                &tree.get_proof(index)
+            ));
+        }
+    }
+
+    #[test]
+    fn should_calculate_fill_index() {
+        let making_amount = U256::from(100);
+
+        // 4 parts of 25, 5 secrets
+        assert_eq!(
+            get_multiple_fill_idx(&making_amount, &U256::from(10), &U256::from(100), 4),
+            Ok(0)
+        );
+        assert_eq!(
+            get_multiple_fill_idx(&making_amount, &U256::from(25), &U256::from(100), 4),
+            Ok(0)
+        );
+        assert_eq!(
+            get_multiple_fill_idx(&making_amount, &U256::from(26), &U256::from(100), 4),
+            Ok(1)
+        );
+        assert_eq!(
+            get_multiple_fill_idx(&making_amount, &U256::from(50), &U256::from(75), 4),
+            Ok(2)
+        );
+        assert_eq!(
+            get_multiple_fill_idx(&making_amount, &U256::from(25), &U256::from(25), 4),
+            Ok(4)
+        );
+        assert_eq!(
+            get_multiple_fill_idx(&making_amount, &U256::from(100), &U256::from(100), 4),
+            Ok(4)
+        );
+    }
+
+    #[test]
+    fn should_fail_to_calculate_index_of_overfill() {
+        assert_eq!(
+            get_multiple_fill_idx(&U256::from(100), &U256::from(30), &U256::from(25), 4),
+            Err("Invalid fill amount".to_string())
+        );
+        assert_eq!(
+            get_multiple_fill_idx(&U256::from(100), &U256::zero(), &U256::from(25), 4),
+            Err("Invalid fill amount".to_string())
+        );
+    }
+
+    #[test]
+    fn should_fail_to_calculate_index_of_overflowing_amount() {
+        assert_eq!(
+            get_multiple_fill_idx(&U256::MAX, &U256::from(30), &U256::from(30), 4),
+            Err("Fill amount overflow".to_string())
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use ethers::types::U256;
+
+use crate::utils::Maskn;
+
+use super::constants::{DEPLOYED_AT_OFFSET, TIMELOCK_STAGE_BITS};
+
+// all stages are seconds relative to escrow deployment time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timelocks {
+    pub src_withdrawal: u32,
+    pub src_public_withdrawal: u32,
+    pub src_cancellation: u32,
+    pub src_public_cancellation: u32,
+    pub dst_withdrawal: u32,
+    pub dst_public_withdrawal: u32,
+    pub dst_cancellation: u32,
+    pub deployed_at: u32,
+}
+
+// stage durations, escrow is not deployed yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimelocksData {
+    pub src_withdrawal: u32,
+    pub src_public_withdrawal: u32,
+    pub src_cancellation: u32,
+    pub src_public_cancellation: u32,
+    pub dst_withdrawal: u32,
+    pub dst_public_withdrawal: u32,
+    pub dst_cancellation: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SrcStage {
+    FinalityLock,
+    PrivateWithdrawal,
+    PublicWithdrawal,
+    PrivateCancellation,
+    PublicCancellation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DstStage {
+    FinalityLock,
+    PrivateWithdrawal,
+    PublicWithdrawal,
+    PrivateCancellation,
+}
+
+impl Timelocks {
+    pub fn new(timelocks: TimelocksData) -> Result<Self, String> {
+        if timelocks.src_withdrawal >= timelocks.src_public_withdrawal {
+            return Err("src_withdrawal should be less than src_public_withdrawal".to_string());
+        }
+
+        if timelocks.src_public_withdrawal >= timelocks.src_cancellation {
+            return Err("src_public_withdrawal should be less than src_cancellation".to_string());
+        }
+
+        if timelocks.src_cancellation >= timelocks.src_public_cancellation {
+            return Err("src_cancellation should be less than src_public_cancellation".to_string());
+        }
+
+        if timelocks.dst_withdrawal >= timelocks.dst_public_withdrawal {
+            return Err("dst_withdrawal should be less than dst_public_withdrawal".to_string());
+        }
+
+        if timelocks.dst_public_withdrawal >= timelocks.dst_cancellation {
+            return Err("dst_public_withdrawal should be less than dst_cancellation".to_string());
+        }
+
+        Ok(Self {
+            src_withdrawal: timelocks.src_withdrawal,
+            src_public_withdrawal: timelocks.src_public_withdrawal,
+            src_cancellation: timelocks.src_cancellation,
+            src_public_cancellation: timelocks.src_public_cancellation,
+            dst_withdrawal: timelocks.dst_withdrawal,
+            dst_public_withdrawal: timelocks.dst_public_withdrawal,
+            dst_cancellation: timelocks.dst_cancellation,
+            deployed_at: 0,
+        })
+    }
+
+    pub fn decode(value: &U256) -> Self {
+        let stage = |index: usize| -> u32 {
+            (value >> (index * TIMELOCK_STAGE_BITS))
+                .maskn(TIMELOCK_STAGE_BITS)
+                .as_u32()
+        };
+
+        Self {
+            src_withdrawal: stage(0),
+            src_public_withdrawal: stage(1),
+            src_cancellation: stage(2),
+            src_public_cancellation: stage(3),
+            dst_withdrawal: stage(4),
+            dst_public_withdrawal: stage(5),
+            dst_cancellation: stage(6),
+            deployed_at: (value >> DEPLOYED_AT_OFFSET)
+                .maskn(TIMELOCK_STAGE_BITS)
+                .as_u32(),
+        }
+    }
+
+    pub fn build(&self) -> U256 {
+        [
+            self.src_withdrawal,
+            self.src_public_withdrawal,
+            self.src_cancellation,
+            self.src_public_cancellation,
+            self.dst_withdrawal,
+            self.dst_public_withdrawal,
+            self.dst_cancellation,
+        ]
+        .iter()
+        .enumerate()
+        .fold(
+            U256::from(self.deployed_at) << DEPLOYED_AT_OFFSET,
+            |acc, (i, stage)| acc | (U256::from(*stage) << (i * TIMELOCK_STAGE_BITS)),
+        )
+    }
+
+    pub fn with_deployed_at(&self, deployed_at: u32) -> Self {
+        Self {
+            deployed_at,
+            ..*self
+        }
+    }
+
+    pub fn get_src_stage(&self, time: u64) -> SrcStage {
+        let deployed_at = self.deployed_at as u64;
+
+        if time < deployed_at + self.src_withdrawal as u64 {
+            SrcStage::FinalityLock
+        } else if time < deployed_at + self.src_public_withdrawal as u64 {
+            SrcStage::PrivateWithdrawal
+        } else if time < deployed_at + self.src_cancellation as u64 {
+            SrcStage::PublicWithdrawal
+        } else if time < deployed_at + self.src_public_cancellation as u64 {
+            SrcStage::PrivateCancellation
+        } else {
+            SrcStage::PublicCancellation
+        }
+    }
+
+    pub fn get_dst_stage(&self, time: u64) -> DstStage {
+        let deployed_at = self.deployed_at as u64;
+
+        if time < deployed_at + self.dst_withdrawal as u64 {
+            DstStage::FinalityLock
+        } else if time < deployed_at + self.dst_public_withdrawal as u64 {
+            DstStage::PrivateWithdrawal
+        } else if time < deployed_at + self.dst_cancellation as u64 {
+            DstStage::PublicWithdrawal
+        } else {
+            DstStage::PrivateCancellation
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::types::U256;
+    use pretty_assertions::assert_eq;
+
+    use super::{DstStage, SrcStage, Timelocks, TimelocksData};
+
+    fn timelocks_data() -> TimelocksData {
+        TimelocksData {
+            src_withdrawal: 10,
+            src_public_withdrawal: 120,
+            src_cancellation: 121,
+            src_public_cancellation: 122,
+            dst_withdrawal: 10,
+            dst_public_withdrawal: 100,
+            dst_cancellation: 101,
+        }
+    }
+
+    fn timelocks() -> Timelocks {
+        Timelocks::new(timelocks_data()).unwrap()
+    }
+
+    #[test]
+    fn should_build_timelocks() {
+        let timelocks = timelocks().with_deployed_at(1673548149);
+
+        assert_eq!(
+            timelocks.build(),
+            U256::from_str("0x63c0517500000065000000640000000a0000007a00000079000000780000000a")
+                .unwrap()
+        );
+        assert_eq!(Timelocks::decode(&timelocks.build()), timelocks);
+    }
+
+    #[test]
+    fn should_get_stages() {
+        let timelocks = timelocks().with_deployed_at(1000);
+
+        assert_eq!(timelocks.get_src_stage(1005), SrcStage::FinalityLock);
+        assert_eq!(timelocks.get_src_stage(1010), SrcStage::PrivateWithdrawal);
+        assert_eq!(timelocks.get_src_stage(1120), SrcStage::PublicWithdrawal);
+        assert_eq!(timelocks.get_src_stage(1121), SrcStage::PrivateCancellation);
+        assert_eq!(timelocks.get_src_stage(2000), SrcStage::PublicCancellation);
+
+        assert_eq!(timelocks.get_dst_stage(1009), DstStage::FinalityLock);
+        assert_eq!(timelocks.get_dst_stage(1050), DstStage::PrivateWithdrawal);
+        assert_eq!(timelocks.get_dst_stage(1100), DstStage::PublicWithdrawal);
+        assert_eq!(timelocks.get_dst_stage(1101), DstStage::PrivateCancellation);
+    }
+
+    #[test]
+    fn should_fail_on_unordered_stages() {
+        assert_eq!(
+            Timelocks::new(TimelocksData {
+                src_public_cancellation: 121,
+                ..timelocks_data()
+            }),
+            Err("src_cancellation should be less than src_public_cancellation".to_string())
+        );
+        assert_eq!(
+            Timelocks::new(TimelocksData {
+                dst_withdrawal: 100,
+                ..timelocks_data()
+            }),
+            Err("dst_withdrawal should be less than dst_public_withdrawal".to_string())
+        );
+    }
+}
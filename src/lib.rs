@@ -1,7 +1,24 @@
+pub mod amount_getter;
 pub mod auction_calculator;
 pub mod auction_salt;
 pub mod auction_suffix;
 pub mod constants;
+pub mod cross_chain;
 pub mod limit_order;
+pub mod limit_order_v4;
+pub mod multicall;
+pub mod order_book;
+pub mod order_classifier;
+pub mod order_state;
+pub mod order_store;
+pub mod permit;
+pub mod profitability;
+pub mod router;
 pub mod salt;
+pub mod settlement;
+#[cfg(feature = "simulation")]
+pub mod simulation;
+#[cfg(test)]
+mod test_utils;
 pub mod utils;
+pub mod validation;
@@ -6,7 +6,7 @@ use serde_json::json;
 use std::collections::BTreeMap;
 use std::str::FromStr;
 
-use crate::constants::{ONE_INCH_ROUTER_V5, ZX};
+use crate::constants::ONE_INCH_ROUTER_V5;
 use crate::limit_order::types::{LimitOrderV3Struct, ToBtreeMap};
 
 use super::domain::{
@@ -55,19 +55,18 @@ pub fn domain_separator(
     let types: BTreeMap<String, Vec<Eip712DomainType>> =
         BTreeMap::from([("EIP712Domain".to_string(), eip712_domain_type())]);
 
-    ZX.to_string()
-        + &hash_struct(
-            "EIP712Domain",
-            &json!({
-                "name": name,
-                "version": version,
-                "chainId": chain_id,
-                "verifyingContract": verifying_contract
-            }),
-            &types,
-        )
-        .unwrap()
-        .encode_hex()
+    hash_struct(
+        "EIP712Domain",
+        &json!({
+            "name": name,
+            "version": version,
+            "chainId": chain_id,
+            "verifyingContract": verifying_contract
+        }),
+        &types,
+    )
+    .unwrap()
+    .encode_hex()
 }
 
 pub fn get_limit_order_v3_domain(chain_id: &U256) -> EIP712Domain {
@@ -79,3 +78,39 @@ pub fn get_limit_order_v3_domain(chain_id: &U256) -> EIP712Domain {
         salt: None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::U256;
+    use pretty_assertions::assert_eq;
+
+    use crate::constants::ONE_INCH_ROUTER_V5;
+
+    use super::{
+        super::domain::{LIMIT_ORDER_V3_TYPE_DATA_NAME, LIMIT_ORDER_V3_TYPE_DATA_VERSION},
+        domain_separator, get_limit_order_v3_domain,
+    };
+
+    #[test]
+    fn should_build_v3_domain_separator() {
+        let separator = domain_separator(
+            LIMIT_ORDER_V3_TYPE_DATA_NAME,
+            LIMIT_ORDER_V3_TYPE_DATA_VERSION,
+            &U256::from(1),
+            ONE_INCH_ROUTER_V5,
+        );
+
+        // "0x" prefixed once, it used to be prepended to already prefixed hex
+        assert_eq!(
+            separator,
+            "0x1c0eb4c27d5b523ca136c0b3b83a4dcac8b70225b38be8507ba1a3f2af03cfca"
+        );
+        assert_eq!(
+            separator,
+            format!(
+                "0x{}",
+                hex::encode(get_limit_order_v3_domain(&U256::from(1)).separator())
+            )
+        );
+    }
+}
@@ -0,0 +1,3 @@
+// every chained interaction is target (20 bytes) | data length (4 bytes) | data
+pub const TARGET_LENGTH: usize = 20;
+pub const DATA_LENGTH_SIZE: usize = 4;
@@ -0,0 +1,243 @@
+pub mod constants;
+
+use ethers::types::{Address, Bytes};
+
+use crate::auction_suffix::types::{AuctionSuffix, PostInteractionData};
+
+use constants::*;
+
+// single call executed by the settlement contract while the order is filled
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interaction {
+    pub target: Address,
+    pub data: Bytes,
+}
+
+impl Interaction {
+    pub fn new(target: Address, data: Bytes) -> Self {
+        Interaction { target, data }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        if self.data.len() > u32::MAX as usize {
+            panic!("interaction data is too long");
+        }
+
+        [
+            self.target.as_bytes(),
+            &(self.data.len() as u32).to_be_bytes(),
+            &self.data,
+        ]
+        .concat()
+    }
+
+    // interactions are executed in the order they are listed
+    pub fn encode_list(interactions: &[Interaction]) -> Bytes {
+        interactions
+            .iter()
+            .flat_map(|interaction| interaction.encode())
+            .collect::<Vec<u8>>()
+            .into()
+    }
+
+    pub fn decode_list(data: &[u8]) -> Result<Vec<Interaction>, String> {
+        let mut interactions = vec![];
+        let mut rest = data;
+
+        while !rest.is_empty() {
+            if rest.len() < TARGET_LENGTH + DATA_LENGTH_SIZE {
+                return Err("Interaction header is too short".to_string());
+            }
+
+            let (target, tail) = rest.split_at(TARGET_LENGTH);
+            let (length, tail) = tail.split_at(DATA_LENGTH_SIZE);
+            let length = u32::from_be_bytes(length.try_into().unwrap()) as usize;
+
+            if tail.len() < length {
+                return Err(format!(
+                    "Interaction data length {} exceeds remaining {} bytes",
+                    length,
+                    tail.len()
+                ));
+            }
+
+            let (data, tail) = tail.split_at(length);
+
+            interactions.push(Interaction::new(
+                Address::from_slice(target),
+                Bytes::from(data.to_vec()),
+            ));
+            rest = tail;
+        }
+
+        Ok(interactions)
+    }
+
+    // settlement is the post interaction target, it gets the chain followed by the suffix
+    pub fn build_post_interaction(
+        interactions: &[Interaction],
+        suffix: &AuctionSuffix,
+        settlement: &Address,
+    ) -> Bytes {
+        PostInteractionData {
+            interaction: Self::encode_list(interactions),
+            suffix: suffix.clone(),
+        }
+        .build(settlement)
+    }
+
+    pub fn parse_post_interaction(
+        post_interaction: &Bytes,
+        settlement: &Address,
+    ) -> Result<(Vec<Interaction>, AuctionSuffix), String> {
+        let data = AuctionSuffix::from_post_interaction(post_interaction, settlement)?;
+
+        Ok((data.interactions()?, data.suffix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::types::{Address, Bytes, U256};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        auction_suffix::{
+            parser::types::AuctionPoint,
+            types::{AuctionSuffix, SettlementSuffixData, TakingFee},
+            whitelist::WhitelistBuilder,
+        },
+        constants::{get_network_address, NetworkEnum, UNWRAPPER_CONTRACT_ADDRESS_MAP},
+        limit_order::{InteractionsData, LimitOrder, OrderInfoData},
+        test_utils::{settlement, usdc, weth},
+    };
+
+    use super::Interaction;
+
+    fn suffix() -> AuctionSuffix {
+        AuctionSuffix::new(SettlementSuffixData {
+            points: vec![AuctionPoint {
+                coefficient: 20000,
+                delay: 12,
+            }],
+            whitelist: WhitelistBuilder::new(1673548139)
+                .add_resolver(
+                    Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+                )
+                .build()
+                .unwrap(),
+            public_resolving_deadline: Some(1673548139),
+            fee: Some(TakingFee {
+                taking_fee_ratio: U256::from(100),
+                taking_fee_receiver: Address::from_low_u64_be(0xfee),
+            }),
+        })
+    }
+
+    fn interactions() -> Vec<Interaction> {
+        vec![
+            // unwrap to maker
+            Interaction::new(
+                get_network_address(&UNWRAPPER_CONTRACT_ADDRESS_MAP, NetworkEnum::ETHEREUM),
+                Bytes::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+            ),
+            Interaction::new(Address::from_low_u64_be(0xbeef), Bytes::default()),
+            Interaction::new(
+                Address::from_low_u64_be(0xcafe),
+                Bytes::from_str("0xdeadbeef").unwrap(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn should_encode_and_decode_interaction_list() {
+        let encoded = Interaction::encode_list(&interactions()[1..]);
+
+        assert_eq!(
+            encoded,
+            Bytes::from_str(
+                "0x000000000000000000000000000000000000beef00000000\
+                000000000000000000000000000000000000cafe00000004deadbeef"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            Interaction::decode_list(&encoded),
+            Ok(interactions()[1..].to_vec())
+        );
+        assert_eq!(Interaction::decode_list(&[]), Ok(vec![]));
+    }
+
+    fn decoded_suffix() -> AuctionSuffix {
+        AuctionSuffix::decode(&Bytes::from_str(&suffix().build()).unwrap())
+    }
+
+    #[test]
+    fn should_build_post_interaction_targeting_settlement() {
+        let post_interaction =
+            Interaction::build_post_interaction(&interactions(), &suffix(), &settlement());
+
+        assert_eq!(&post_interaction[..20], settlement().as_bytes());
+        assert_eq!(
+            Interaction::parse_post_interaction(&post_interaction, &settlement()),
+            Ok((interactions(), decoded_suffix()))
+        );
+        assert!(Interaction::parse_post_interaction(
+            &post_interaction,
+            &Address::from_low_u64_be(0xbad)
+        )
+        .unwrap_err()
+        .contains("is not settlement contract"));
+    }
+
+    #[test]
+    fn should_extract_chained_interactions_from_order() {
+        let order = LimitOrder::new(
+            &OrderInfoData {
+                maker_asset: weth(),
+                taker_asset: usdc(),
+                making_amount: U256::from(1000),
+                taking_amount: U256::from(2000),
+                maker: Address::from_low_u64_be(1),
+                salt: None,
+                allowed_sender: Some(settlement()),
+                receiver: None,
+            },
+            Some(&InteractionsData {
+                post_interaction: Some(Interaction::build_post_interaction(
+                    &interactions(),
+                    &suffix(),
+                    &settlement(),
+                )),
+                ..Default::default()
+            }),
+            &|| U256::zero(),
+        )
+        .build();
+
+        let extracted = AuctionSuffix::from_limit_order_v3_struct(&order, &settlement()).unwrap();
+
+        assert_eq!(extracted.suffix, decoded_suffix());
+        assert_eq!(extracted.interactions(), Ok(interactions()));
+    }
+
+    #[test]
+    fn should_reject_malformed_list() {
+        let encoded = Interaction::encode_list(&interactions());
+
+        assert_eq!(
+            Interaction::decode_list(&encoded[..10]),
+            Err("Interaction header is too short".to_string())
+        );
+        assert_eq!(
+            Interaction::decode_list(&encoded[..30]),
+            Err("Interaction data length 20 exceeds remaining 6 bytes".to_string())
+        );
+        assert_eq!(
+            Interaction::parse_post_interaction(&Bytes::default(), &settlement()),
+            Err("Post interaction is too short".to_string())
+        );
+    }
+}
@@ -7,8 +7,10 @@ use ethers::types::{
 
 use crate::{
     constants::{ZERO_ADDRESS, ZX},
-    limit_order::eip712::order_typed_data_builder::get_limit_order_v3_domain,
-    salt::build_salt,
+    limit_order::{
+        eip712::order_typed_data_builder::get_limit_order_v3_domain, predicate::Predicate,
+    },
+    salt::{RandomSalt, SaltGenerator},
     utils::cumsum,
 };
 
@@ -19,9 +21,12 @@ use self::{
 };
 
 pub mod eip712;
+pub mod interactions;
 pub mod parser;
+pub mod predicate;
 pub mod types;
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct LimitOrder {
     maker_asset: Address,
     taker_asset: Address,
@@ -41,6 +46,7 @@ pub struct LimitOrder {
     salt: U256,
 }
 
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct InteractionsData {
     pub maker_asset_data: Option<Bytes>,
     pub taker_asset_data: Option<Bytes>,
@@ -52,19 +58,27 @@ pub struct InteractionsData {
     pub post_interaction: Option<Bytes>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct OrderInfoData {
-    maker_asset: Address,
-    taker_asset: Address,
-    making_amount: U256,
-    taking_amount: U256,
-    maker: Address,
-    salt: Option<U256>,
-    allowed_sender: Option<Address>,
-    receiver: Option<Address>,
+    pub maker_asset: Address,
+    pub taker_asset: Address,
+    pub making_amount: U256,
+    pub taking_amount: U256,
+    pub maker: Address,
+    pub salt: Option<U256>,
+    pub allowed_sender: Option<Address>,
+    pub receiver: Option<Address>,
 }
 
 impl LimitOrder {
-    pub fn new(order_info: &OrderInfoData, interactions: Option<&InteractionsData>) -> Self {
+    pub fn new<G>(
+        order_info: &OrderInfoData,
+        interactions: Option<&InteractionsData>,
+        salt_generator: &G,
+    ) -> Self
+    where
+        G: SaltGenerator + ?Sized,
+    {
         let interactions = interactions.unwrap_or(&InteractionsData {
             maker_asset_data: None,
             taker_asset_data: None,
@@ -81,7 +95,7 @@ impl LimitOrder {
             taker_asset: order_info.taker_asset,
             making_amount: order_info.making_amount,
             taking_amount: order_info.taking_amount,
-            salt: *order_info.salt.as_ref().unwrap_or(&build_salt()),
+            salt: order_info.salt.unwrap_or_else(|| salt_generator.generate()),
             from: order_info.maker,
             allowed_sender: *order_info.allowed_sender.as_ref().unwrap_or(&ZERO_ADDRESS),
             receiver: *order_info.receiver.as_ref().unwrap_or(&ZERO_ADDRESS),
@@ -188,6 +202,7 @@ impl LimitOrder {
                 pre_interaction: Some(pre_interaction),
                 post_interaction: Some(post_interaction),
             }),
+            &RandomSalt,
         )
     }
 
@@ -243,6 +258,240 @@ impl LimitOrder {
 
         get_order_hash(self.get_typed_data(Some(&domain)))
     }
+
+    pub fn maker_asset(&self) -> Address {
+        self.maker_asset
+    }
+
+    pub fn taker_asset(&self) -> Address {
+        self.taker_asset
+    }
+
+    pub fn making_amount(&self) -> U256 {
+        self.making_amount
+    }
+
+    pub fn taking_amount(&self) -> U256 {
+        self.taking_amount
+    }
+
+    pub fn maker(&self) -> Address {
+        self.from
+    }
+
+    pub fn allowed_sender(&self) -> Address {
+        self.allowed_sender
+    }
+
+    pub fn receiver(&self) -> Address {
+        self.receiver
+    }
+
+    pub fn salt(&self) -> U256 {
+        self.salt
+    }
+
+    pub fn maker_asset_data(&self) -> &Bytes {
+        &self.maker_asset_data
+    }
+
+    pub fn taker_asset_data(&self) -> &Bytes {
+        &self.taker_asset_data
+    }
+
+    pub fn get_making_amount(&self) -> &Bytes {
+        &self.get_making_amount
+    }
+
+    pub fn get_taking_amount(&self) -> &Bytes {
+        &self.get_taking_amount
+    }
+
+    pub fn predicate(&self) -> &Bytes {
+        &self.predicate
+    }
+
+    pub fn permit(&self) -> &Bytes {
+        &self.permit
+    }
+
+    pub fn pre_interaction(&self) -> &Bytes {
+        &self.pre_interaction
+    }
+
+    pub fn post_interaction(&self) -> &Bytes {
+        &self.post_interaction
+    }
+
+    pub fn interactions(&self) -> InteractionsData {
+        InteractionsData {
+            maker_asset_data: Some(self.maker_asset_data.clone()),
+            taker_asset_data: Some(self.taker_asset_data.clone()),
+            get_making_amount: Some(self.get_making_amount.clone()),
+            get_taking_amount: Some(self.get_taking_amount.clone()),
+            predicate: Some(self.predicate.clone()),
+            permit: Some(self.permit.clone()),
+            pre_interaction: Some(self.pre_interaction.clone()),
+            post_interaction: Some(self.post_interaction.clone()),
+        }
+    }
+
+    pub fn with_maker_asset(&self, maker_asset: Address) -> Self {
+        Self {
+            maker_asset,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_taker_asset(&self, taker_asset: Address) -> Self {
+        Self {
+            taker_asset,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_making_amount(&self, making_amount: U256) -> Self {
+        Self {
+            making_amount,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_taking_amount(&self, taking_amount: U256) -> Self {
+        Self {
+            taking_amount,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_maker(&self, maker: Address) -> Self {
+        Self {
+            from: maker,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_allowed_sender(&self, allowed_sender: Address) -> Self {
+        Self {
+            allowed_sender,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_receiver(&self, receiver: Address) -> Self {
+        Self {
+            receiver,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_salt(&self, salt: U256) -> Self {
+        Self {
+            salt,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_maker_asset_data(&self, maker_asset_data: Bytes) -> Self {
+        Self {
+            maker_asset_data,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_taker_asset_data(&self, taker_asset_data: Bytes) -> Self {
+        Self {
+            taker_asset_data,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_get_making_amount(&self, get_making_amount: Bytes) -> Self {
+        Self {
+            get_making_amount,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_get_taking_amount(&self, get_taking_amount: Bytes) -> Self {
+        Self {
+            get_taking_amount,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_predicate(&self, predicate: Bytes) -> Self {
+        Self {
+            predicate,
+            ..self.clone()
+        }
+    }
+
+    // order becomes invalid once maker increases the nonce on the router
+    pub fn with_nonce(&self, nonce: u64) -> Self {
+        let predicate = Predicate::bind_nonce(Predicate::decode(&self.predicate), self.from, nonce);
+
+        self.with_predicate(predicate.build())
+    }
+
+    pub fn with_permit(&self, permit: Bytes) -> Self {
+        Self {
+            permit,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_pre_interaction(&self, pre_interaction: Bytes) -> Self {
+        Self {
+            pre_interaction,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_post_interaction(&self, post_interaction: Bytes) -> Self {
+        Self {
+            post_interaction,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_interactions(&self, interactions: &InteractionsData) -> Self {
+        Self {
+            maker_asset_data: interactions
+                .maker_asset_data
+                .clone()
+                .unwrap_or_else(|| self.maker_asset_data.clone()),
+            taker_asset_data: interactions
+                .taker_asset_data
+                .clone()
+                .unwrap_or_else(|| self.taker_asset_data.clone()),
+            get_making_amount: interactions
+                .get_making_amount
+                .clone()
+                .unwrap_or_else(|| self.get_making_amount.clone()),
+            get_taking_amount: interactions
+                .get_taking_amount
+                .clone()
+                .unwrap_or_else(|| self.get_taking_amount.clone()),
+            predicate: interactions
+                .predicate
+                .clone()
+                .unwrap_or_else(|| self.predicate.clone()),
+            permit: interactions
+                .permit
+                .clone()
+                .unwrap_or_else(|| self.permit.clone()),
+            pre_interaction: interactions
+                .pre_interaction
+                .clone()
+                .unwrap_or_else(|| self.pre_interaction.clone()),
+            post_interaction: interactions
+                .post_interaction
+                .clone()
+                .unwrap_or_else(|| self.post_interaction.clone()),
+            ..self.clone()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -261,7 +510,9 @@ mod tests {
 
     use crate::limit_order::types::LimitOrderV3Struct;
 
-    use super::{LimitOrder, OrderInfoData};
+    use crate::salt::RandomSalt;
+
+    use super::{predicate::Predicate, InteractionsData, LimitOrder, OrderInfoData};
 
     #[test]
     fn should_create_limit_order() {
@@ -279,6 +530,7 @@ mod tests {
                 receiver: None,
             },
             None,
+            &RandomSalt,
         );
 
         assert_eq!(
@@ -325,6 +577,162 @@ mod tests {
         assert_eq!(order.build(), order_struct)
     }
 
+    #[test]
+    fn should_expose_fields_of_decoded_limit_order() {
+        let order_struct = LimitOrderV3Struct {
+            allowed_sender: Address::from_str("0x0000000000000000000000000000000000000000").unwrap(),
+            interactions: Bytes::from_str("0x63592c2b0000000000000000000000000000000000000000000000000000000063c0566a08b067ad41e45babe5bbb52fc2fe7f692f628b0600000000219ab540356cbb839cbe05303d7705fa".trim_start_matches("0x")).unwrap(),
+            maker: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+            maker_asset: Address::from_str("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2").unwrap(),
+            making_amount: U256::from(1000000000000000000u64),
+            offsets: U256::from_dec_str("2048955946929424286921227713067743020696385405755235979139736848564224").unwrap(),
+            receiver: Address::from_str("0x0000000000000000000000000000000000000000").unwrap(),
+            salt: U256::from(1673549418040u64),
+            taker_asset: Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap(),
+            taking_amount: U256::from(1420000000u64),
+        };
+
+        let order = LimitOrder::decode(&order_struct);
+
+        assert_eq!(order.maker(), order_struct.maker);
+        assert_eq!(order.maker_asset(), order_struct.maker_asset);
+        assert_eq!(order.taker_asset(), order_struct.taker_asset);
+        assert_eq!(order.receiver(), order_struct.receiver);
+        assert_eq!(order.salt(), order_struct.salt);
+        assert_eq!(
+            order.predicate(),
+            &Bytes::from_str(
+                "0x63592c2b0000000000000000000000000000000000000000000000000000000063c0566a"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            order.post_interaction(),
+            &Bytes::from_str("0x08b067ad41e45babe5bbb52fc2fe7f692f628b0600000000219ab540356cbb839cbe05303d7705fa").unwrap()
+        );
+        assert_eq!(
+            order.interactions().predicate,
+            Some(order.predicate().to_owned())
+        );
+        assert_eq!(order.permit(), &Bytes::from_str("0x").unwrap());
+    }
+
+    #[test]
+    fn should_amend_limit_order_with_new_salt() {
+        let limit_order = LimitOrder::new(
+            &OrderInfoData {
+                maker_asset: Address::from_str("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")
+                    .unwrap(),
+                taker_asset: Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")
+                    .unwrap(),
+                making_amount: U256::from(1000000000000000000u64),
+                taking_amount: U256::from(1420000000),
+                maker: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+                salt: Some(U256::from(1)),
+                allowed_sender: None,
+                receiver: None,
+            },
+            None,
+            &RandomSalt,
+        );
+
+        let amended = limit_order.with_salt(U256::from(1673549418040u64));
+
+        assert_eq!(limit_order.salt(), U256::from(1));
+        assert_eq!(amended.salt(), U256::from(1673549418040u64));
+        assert_eq!(amended.maker(), limit_order.maker());
+        assert_eq!(
+            amended.get_order_hash(None),
+            "0x4bdb758d3d4b265367c461cdb12b2fbe92fd8f2bcc9423393e9da4490d6157c4"
+        );
+    }
+
+    #[test]
+    fn should_amend_limit_order_interactions() {
+        let limit_order = LimitOrder::new(
+            &OrderInfoData {
+                maker_asset: Address::from_str("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")
+                    .unwrap(),
+                taker_asset: Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")
+                    .unwrap(),
+                making_amount: U256::from(1000000000000000000u64),
+                taking_amount: U256::from(1420000000),
+                maker: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+                salt: Some(U256::from(1673549418040u64)),
+                allowed_sender: None,
+                receiver: None,
+            },
+            None,
+            &RandomSalt,
+        );
+
+        let post_interaction =
+            Bytes::from_str("0x08b067ad41e45babe5bbb52fc2fe7f692f628b06").unwrap();
+
+        let amended = limit_order.with_interactions(&InteractionsData {
+            maker_asset_data: None,
+            taker_asset_data: None,
+            get_making_amount: None,
+            get_taking_amount: None,
+            predicate: None,
+            permit: None,
+            pre_interaction: None,
+            post_interaction: Some(post_interaction.clone()),
+        });
+
+        assert_eq!(amended.post_interaction(), &post_interaction);
+        assert_eq!(amended.build().interactions, post_interaction);
+        assert_eq!(amended.with_post_interaction(Bytes::default()), limit_order);
+    }
+
+    #[test]
+    fn should_bind_limit_order_to_nonce() {
+        let limit_order = LimitOrder::new(
+            &OrderInfoData {
+                maker_asset: Address::from_str("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")
+                    .unwrap(),
+                taker_asset: Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")
+                    .unwrap(),
+                making_amount: U256::from(1000000000000000000u64),
+                taking_amount: U256::from(1420000000),
+                maker: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+                salt: Some(U256::from(1673549418040u64)),
+                allowed_sender: None,
+                receiver: None,
+            },
+            None,
+            &RandomSalt,
+        )
+        .with_predicate(Predicate::TimestampBelow(1673548200).build());
+
+        let bound = limit_order.with_nonce(7);
+
+        assert_eq!(
+            Predicate::decode(bound.predicate()),
+            Some(Predicate::TimestampBelowAndNonceEquals {
+                time: 1673548200,
+                nonce: 7,
+                account: limit_order.maker(),
+            })
+        );
+
+        // binding again moves the order to the new nonce
+        assert_eq!(bound.with_nonce(8), limit_order.with_nonce(8));
+        assert_eq!(
+            Predicate::decode(
+                limit_order
+                    .with_predicate(Bytes::default())
+                    .with_nonce(7)
+                    .with_nonce(8)
+                    .predicate()
+            ),
+            Some(Predicate::NonceEquals {
+                account: limit_order.maker(),
+                nonce: U256::from(8),
+            })
+        );
+    }
+
     #[test]
     fn should_get_limit_order_typed_data() {
         let limit_order = LimitOrder::new(
@@ -341,6 +749,7 @@ mod tests {
                 receiver: None,
             },
             None,
+            &RandomSalt,
         );
 
         let expected_message: BTreeMap<String, serde_json::Value> = BTreeMap::from([
@@ -474,6 +883,7 @@ mod tests {
                 receiver: None,
             },
             None,
+            &RandomSalt,
         );
 
         assert_eq!(
@@ -6,29 +6,54 @@ use crate::utils::Maskn;
 
 type ParsedInteractions = HashMap<String, Bytes>;
 
-pub fn parse_interactions(offsets: &U256, interactions: &Bytes) -> ParsedInteractions {
-    let layout = vec![
-        "maker_asset_data".to_string(),
-        "taker_asset_data".to_string(),
-        "get_making_amount".to_string(),
-        "get_taking_amount".to_string(),
-        "predicate".to_string(),
-        "permit".to_string(),
-        "pre_interaction".to_string(),
-        "post_interaction".to_string(),
-    ];
+pub const INTERACTIONS_LAYOUT: [&str; 8] = [
+    "maker_asset_data",
+    "taker_asset_data",
+    "get_making_amount",
+    "get_taking_amount",
+    "predicate",
+    "permit",
+    "pre_interaction",
+    "post_interaction",
+];
 
+pub fn parse_interactions(offsets: &U256, interactions: &Bytes) -> ParsedInteractions {
     let mut parsed_interactions = ParsedInteractions::new();
 
-    for (index, element) in layout.iter().enumerate() {
+    for (index, element) in INTERACTIONS_LAYOUT.iter().enumerate() {
         let interaction = parse_interaction_field(&offsets, &interactions, index as u8);
 
-        parsed_interactions.insert(element.to_owned(), interaction);
+        parsed_interactions.insert(element.to_string(), interaction);
     }
 
     parsed_interactions
 }
 
+// every field should end after the previous one and the last one at the end of interactions
+pub fn validate_offsets(offsets: &U256, interactions: &Bytes) -> Result<(), String> {
+    let mut prev_to_byte = 0;
+
+    for field in 0..INTERACTIONS_LAYOUT.len() as u8 {
+        let (_, to_byte) = get_offset_for_interaction(offsets, field);
+
+        if to_byte < prev_to_byte {
+            return Err(format!("Offset of interaction {} is out of order", field));
+        }
+
+        prev_to_byte = to_byte;
+    }
+
+    if prev_to_byte != interactions.len() {
+        return Err(format!(
+            "Offsets point to {} bytes of interactions, got {}",
+            prev_to_byte,
+            interactions.len()
+        ));
+    }
+
+    Ok(())
+}
+
 pub fn parse_interaction_field(offsets: &U256, interactions: &Bytes, field: u8) -> Bytes {
     let (from_byte, to_byte) = get_offset_for_interaction(offsets, field);
 
@@ -48,3 +73,32 @@ pub fn get_offset_for_interaction(offsets: &U256, field: u8) -> (usize, usize) {
 
     return (from_byte.as_usize(), to_byte.as_usize());
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::types::{Bytes, U256};
+    use pretty_assertions::assert_eq;
+
+    use super::validate_offsets;
+
+    #[test]
+    fn should_validate_offsets() {
+        let interactions = Bytes::from_str("0x63592c2b0000000000000000000000000000000000000000000000000000000063c0566a08b067ad41e45babe5bbb52fc2fe7f692f628b0600000000219ab540356cbb839cbe05303d7705fa").unwrap();
+        let offsets = U256::from_dec_str(
+            "2048955946929424286921227713067743020696385405755235979139736848564224",
+        )
+        .unwrap();
+
+        assert_eq!(validate_offsets(&offsets, &interactions), Ok(()));
+        assert_eq!(
+            validate_offsets(&offsets, &Bytes::from_str("0x63592c2b").unwrap()),
+            Err("Offsets point to 76 bytes of interactions, got 4".to_string())
+        );
+        assert_eq!(
+            validate_offsets(&U256::from(1), &Bytes::from_str("0x63").unwrap()),
+            Err("Offset of interaction 1 is out of order".to_string())
+        );
+    }
+}
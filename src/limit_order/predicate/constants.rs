@@ -0,0 +1,22 @@
+// https://github.com/1inch/limit-order-protocol/blob/v3.0.1/contracts/helpers/PredicateHelper.sol
+pub const OR: &str = "or(uint256,bytes)";
+pub const AND: &str = "and(uint256,bytes)";
+pub const NOT: &str = "not(bytes)";
+pub const EQ: &str = "eq(uint256,bytes)";
+pub const LT: &str = "lt(uint256,bytes)";
+pub const GT: &str = "gt(uint256,bytes)";
+pub const TIMESTAMP_BELOW: &str = "timestampBelow(uint256)";
+pub const ARBITRARY_STATIC_CALL: &str = "arbitraryStaticCall(address,bytes)";
+
+// https://github.com/1inch/limit-order-protocol/blob/v3.0.1/contracts/helpers/NonceManager.sol
+pub const NONCE_EQUALS: &str = "nonceEquals(address,uint256)";
+pub const TIMESTAMP_BELOW_AND_NONCE_EQUALS: &str = "timestampBelowAndNonceEquals(uint256)";
+
+// timeNonceAccount = time (48 bits) | nonce (48 bits) | account (160 bits)
+pub const TIME_SHIFT: usize = 208;
+pub const NONCE_SHIFT: usize = 160;
+pub const TIME_NONCE_BITS: usize = 48;
+
+// and/or pack end offsets of calls into 32 bit chunks
+pub const OFFSET_BITS: usize = 32;
+pub const MAX_CALLS_COUNT: usize = 8;
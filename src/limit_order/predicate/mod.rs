@@ -0,0 +1,509 @@
+pub mod constants;
+
+use ethers::{
+    abi::{decode, encode, ParamType, Token},
+    types::{Address, Bytes, U256},
+    utils::id,
+};
+
+use crate::utils::Maskn;
+
+use constants::*;
+
+// predicate of limit order v3, which is a calldata of static call to the protocol itself
+#[derive(Debug, Clone, PartialEq)]
+pub enum Predicate {
+    Or(Vec<Predicate>),
+    And(Vec<Predicate>),
+    Not(Box<Predicate>),
+    Eq(U256, Box<Predicate>),
+    Lt(U256, Box<Predicate>),
+    Gt(U256, Box<Predicate>),
+    TimestampBelow(u64),
+    NonceEquals {
+        account: Address,
+        nonce: U256,
+    },
+    TimestampBelowAndNonceEquals {
+        time: u64,
+        nonce: u64,
+        account: Address,
+    },
+    ArbitraryStaticCall {
+        target: Address,
+        data: Bytes,
+    },
+    // any call which is not known to the parser
+    Unknown(Bytes),
+}
+
+impl Predicate {
+    // None for an empty predicate, which is always true
+    pub fn decode(predicate: &Bytes) -> Option<Self> {
+        if predicate.is_empty() {
+            return None;
+        }
+
+        Some(Self::decode_call(predicate))
+    }
+
+    pub fn build(&self) -> Bytes {
+        let (signature, tokens) = match self {
+            Predicate::Or(calls) => (OR, Self::pack_calls(calls)),
+            Predicate::And(calls) => (AND, Self::pack_calls(calls)),
+            Predicate::Not(call) => (NOT, vec![Token::Bytes(call.build().to_vec())]),
+            Predicate::Eq(value, call) => (EQ, Self::compare_tokens(value, call)),
+            Predicate::Lt(value, call) => (LT, Self::compare_tokens(value, call)),
+            Predicate::Gt(value, call) => (GT, Self::compare_tokens(value, call)),
+            Predicate::TimestampBelow(time) => {
+                (TIMESTAMP_BELOW, vec![Token::Uint(U256::from(*time))])
+            }
+            Predicate::NonceEquals { account, nonce } => (
+                NONCE_EQUALS,
+                vec![Token::Address(*account), Token::Uint(*nonce)],
+            ),
+            Predicate::TimestampBelowAndNonceEquals {
+                time,
+                nonce,
+                account,
+            } => {
+                if *time >= 1 << TIME_NONCE_BITS || *nonce >= 1 << TIME_NONCE_BITS {
+                    panic!("time and nonce should fit into 48 bits");
+                }
+
+                let time_nonce_account = (U256::from(*time) << TIME_SHIFT)
+                    | (U256::from(*nonce) << NONCE_SHIFT)
+                    | U256::from(account.as_bytes());
+
+                (
+                    TIMESTAMP_BELOW_AND_NONCE_EQUALS,
+                    vec![Token::Uint(time_nonce_account)],
+                )
+            }
+            Predicate::ArbitraryStaticCall { target, data } => (
+                ARBITRARY_STATIC_CALL,
+                vec![Token::Address(*target), Token::Bytes(data.to_vec())],
+            ),
+            Predicate::Unknown(data) => return data.clone(),
+        };
+
+        [id(signature).to_vec(), encode(&tokens)].concat().into()
+    }
+
+    // binds predicate to the nonce of account,
+    // so increaseNonce/advanceNonce invalidates every order sharing the nonce,
+    // an existing nonce check of the account is replaced
+    pub fn bind_nonce(predicate: Option<Predicate>, account: Address, nonce: u64) -> Self {
+        let nonce_equals = Predicate::NonceEquals {
+            account,
+            nonce: U256::from(nonce),
+        };
+
+        match predicate {
+            None => nonce_equals,
+            Some(Predicate::NonceEquals {
+                account: nonce_account,
+                ..
+            }) if nonce_account == account => nonce_equals,
+            Some(Predicate::TimestampBelowAndNonceEquals {
+                time,
+                account: nonce_account,
+                ..
+            }) if nonce_account == account => {
+                Self::bind_nonce(Some(Predicate::TimestampBelow(time)), account, nonce)
+            }
+            Some(Predicate::TimestampBelow(time))
+                if time < 1 << TIME_NONCE_BITS && nonce < 1 << TIME_NONCE_BITS =>
+            {
+                Predicate::TimestampBelowAndNonceEquals {
+                    time,
+                    nonce,
+                    account,
+                }
+            }
+            Some(Predicate::And(mut calls)) => {
+                let nonce_check = calls.iter().position(|call| {
+                    matches!(
+                        call,
+                        Predicate::NonceEquals { .. }
+                            | Predicate::TimestampBelowAndNonceEquals { .. }
+                    ) && call.nonce(&account).is_some()
+                });
+
+                match nonce_check {
+                    Some(index) => {
+                        calls[index] = Self::bind_nonce(Some(calls[index].clone()), account, nonce);
+                    }
+                    None if calls.len() < MAX_CALLS_COUNT => calls.push(nonce_equals),
+                    None => return Predicate::And(vec![Predicate::And(calls), nonce_equals]),
+                }
+
+                Predicate::And(calls)
+            }
+            Some(predicate) => Predicate::And(vec![predicate, nonce_equals]),
+        }
+    }
+
+    // nonce of account the predicate is bound to
+    pub fn nonce(&self, account: &Address) -> Option<U256> {
+        match self {
+            Predicate::NonceEquals {
+                account: nonce_account,
+                nonce,
+            } if nonce_account == account => Some(*nonce),
+            Predicate::TimestampBelowAndNonceEquals {
+                account: nonce_account,
+                nonce,
+                ..
+            } if nonce_account == account => Some(U256::from(*nonce)),
+            Predicate::And(calls) => calls.iter().find_map(|c| c.nonce(account)),
+            _ => None,
+        }
+    }
+
+    // timestamp after which predicate is guaranteed to fail
+    pub fn expiration(&self) -> Option<u64> {
+        match self {
+            Predicate::TimestampBelow(time)
+            | Predicate::TimestampBelowAndNonceEquals { time, .. } => Some(*time),
+            Predicate::And(calls) => calls.iter().filter_map(|c| c.expiration()).min(),
+            Predicate::Or(calls) => calls
+                .iter()
+                .map(|c| c.expiration())
+                .collect::<Option<Vec<u64>>>()
+                .and_then(|expirations| expirations.into_iter().max()),
+            _ => None,
+        }
+    }
+
+    fn decode_call(data: &[u8]) -> Self {
+        let unknown = || Predicate::Unknown(Bytes::from(data.to_vec()));
+
+        if data.len() < 4 {
+            return unknown();
+        }
+
+        let selector = &data[0..4];
+        let args = &data[4..];
+
+        let signature = [
+            OR,
+            AND,
+            NOT,
+            EQ,
+            LT,
+            GT,
+            TIMESTAMP_BELOW,
+            NONCE_EQUALS,
+            TIMESTAMP_BELOW_AND_NONCE_EQUALS,
+            ARBITRARY_STATIC_CALL,
+        ]
+        .into_iter()
+        .find(|signature| id(signature) == selector);
+
+        let signature = match signature {
+            Some(signature) => signature,
+            None => return unknown(),
+        };
+
+        let mut tokens = match decode(&Self::param_types(signature), args) {
+            Ok(tokens) => tokens.into_iter(),
+            Err(_) => return unknown(),
+        };
+        let mut next = || tokens.next().unwrap();
+
+        match signature {
+            OR | AND => {
+                let offsets = next().into_uint().unwrap();
+                let data = next().into_bytes().unwrap();
+
+                match Self::unpack_calls(&offsets, &data) {
+                    Some(calls) if signature == OR => Predicate::Or(calls),
+                    Some(calls) => Predicate::And(calls),
+                    None => unknown(),
+                }
+            }
+            NOT => Predicate::Not(Box::new(Self::decode_call(&next().into_bytes().unwrap()))),
+            EQ | LT | GT => {
+                let value = next().into_uint().unwrap();
+                let call = Box::new(Self::decode_call(&next().into_bytes().unwrap()));
+
+                match signature {
+                    EQ => Predicate::Eq(value, call),
+                    LT => Predicate::Lt(value, call),
+                    _ => Predicate::Gt(value, call),
+                }
+            }
+            TIMESTAMP_BELOW => match u64::try_from(next().into_uint().unwrap()) {
+                Ok(time) => Predicate::TimestampBelow(time),
+                Err(_) => unknown(),
+            },
+            NONCE_EQUALS => Predicate::NonceEquals {
+                account: next().into_address().unwrap(),
+                nonce: next().into_uint().unwrap(),
+            },
+            TIMESTAMP_BELOW_AND_NONCE_EQUALS => {
+                let time_nonce_account = next().into_uint().unwrap();
+                let mut account = [0u8; 32];
+                time_nonce_account.to_big_endian(&mut account);
+
+                Predicate::TimestampBelowAndNonceEquals {
+                    time: (time_nonce_account >> TIME_SHIFT)
+                        .maskn(TIME_NONCE_BITS)
+                        .as_u64(),
+                    nonce: (time_nonce_account >> NONCE_SHIFT)
+                        .maskn(TIME_NONCE_BITS)
+                        .as_u64(),
+                    account: Address::from_slice(&account[12..]),
+                }
+            }
+            _ => Predicate::ArbitraryStaticCall {
+                target: next().into_address().unwrap(),
+                data: next().into_bytes().unwrap().into(),
+            },
+        }
+    }
+
+    fn param_types(signature: &str) -> Vec<ParamType> {
+        match signature {
+            OR | AND | EQ | LT | GT => vec![ParamType::Uint(256), ParamType::Bytes],
+            NOT => vec![ParamType::Bytes],
+            TIMESTAMP_BELOW | TIMESTAMP_BELOW_AND_NONCE_EQUALS => vec![ParamType::Uint(256)],
+            NONCE_EQUALS => vec![ParamType::Address, ParamType::Uint(256)],
+            _ => vec![ParamType::Address, ParamType::Bytes],
+        }
+    }
+
+    fn compare_tokens(value: &U256, call: &Predicate) -> Vec<Token> {
+        vec![Token::Uint(*value), Token::Bytes(call.build().to_vec())]
+    }
+
+    fn pack_calls(calls: &[Predicate]) -> Vec<Token> {
+        if calls.is_empty() || calls.len() > MAX_CALLS_COUNT {
+            panic!("calls count should be from 1 to {}", MAX_CALLS_COUNT);
+        }
+
+        let mut offsets = U256::zero();
+        let mut data = vec![];
+
+        for (i, call) in calls.iter().enumerate() {
+            data.extend_from_slice(&call.build());
+            offsets |= U256::from(data.len()) << (i * OFFSET_BITS);
+        }
+
+        vec![Token::Uint(offsets), Token::Bytes(data)]
+    }
+
+    fn unpack_calls(offsets: &U256, data: &[u8]) -> Option<Vec<Predicate>> {
+        let mut calls = vec![];
+        let mut previous = 0;
+
+        for i in 0..MAX_CALLS_COUNT {
+            let current = (offsets >> (i * OFFSET_BITS)).maskn(OFFSET_BITS).as_usize();
+
+            if current == 0 {
+                break;
+            }
+
+            if current < previous || current > data.len() {
+                return None;
+            }
+
+            calls.push(Self::decode_call(&data[previous..current]));
+            previous = current;
+        }
+
+        Some(calls)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::types::{Address, Bytes, U256};
+    use pretty_assertions::assert_eq;
+
+    use super::Predicate;
+
+    fn maker() -> Address {
+        Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap()
+    }
+
+    #[test]
+    fn should_build_timestamp_below() {
+        assert_eq!(
+            Predicate::TimestampBelow(1673548200).build(),
+            Bytes::from_str(
+                "0x63592c2b0000000000000000000000000000000000000000000000000000000063c051a8"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn should_pack_time_nonce_account() {
+        let predicate = Predicate::TimestampBelowAndNonceEquals {
+            time: 1673548200,
+            nonce: 3,
+            account: maker(),
+        }
+        .build();
+
+        assert_eq!(
+            Bytes::from(predicate[4..].to_vec()),
+            Bytes::from_str("0x000063c051a800000000000300000000219ab540356cbb839cbe05303d7705fa")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn should_roundtrip_nested_predicate() {
+        let predicate = Predicate::And(vec![
+            Predicate::TimestampBelow(1673548200),
+            Predicate::Or(vec![
+                Predicate::NonceEquals {
+                    account: maker(),
+                    nonce: U256::from(1),
+                },
+                Predicate::Not(Box::new(Predicate::TimestampBelow(1673548100))),
+            ]),
+            Predicate::Gt(
+                U256::from(100),
+                Box::new(Predicate::ArbitraryStaticCall {
+                    target: maker(),
+                    data: Bytes::from_str("0xdeadbeef").unwrap(),
+                }),
+            ),
+        ]);
+
+        assert_eq!(Predicate::decode(&predicate.build()), Some(predicate));
+    }
+
+    #[test]
+    fn should_keep_unknown_calls() {
+        let unknown = Bytes::from_str("0x12345678").unwrap();
+
+        assert_eq!(Predicate::decode(&Bytes::default()), None);
+        assert_eq!(
+            Predicate::decode(&unknown),
+            Some(Predicate::Unknown(unknown))
+        );
+    }
+
+    #[test]
+    fn should_bind_predicate_to_nonce() {
+        assert_eq!(
+            Predicate::bind_nonce(None, maker(), 3),
+            Predicate::NonceEquals {
+                account: maker(),
+                nonce: U256::from(3)
+            }
+        );
+        assert_eq!(
+            Predicate::bind_nonce(Some(Predicate::TimestampBelow(1673548200)), maker(), 3),
+            Predicate::TimestampBelowAndNonceEquals {
+                time: 1673548200,
+                nonce: 3,
+                account: maker(),
+            }
+        );
+
+        let bound = Predicate::bind_nonce(
+            Some(Predicate::Not(Box::new(Predicate::TimestampBelow(100)))),
+            maker(),
+            3,
+        );
+
+        assert_eq!(bound.nonce(&maker()), Some(U256::from(3)));
+        assert_eq!(bound.nonce(&Address::zero()), None);
+
+        // time which does not fit into 48 bits is kept in a separate call
+        let late = Predicate::bind_nonce(Some(Predicate::TimestampBelow(1 << 48)), maker(), 3);
+
+        assert_eq!(
+            late,
+            Predicate::And(vec![
+                Predicate::TimestampBelow(1 << 48),
+                Predicate::NonceEquals {
+                    account: maker(),
+                    nonce: U256::from(3)
+                }
+            ])
+        );
+        assert_eq!(Predicate::decode(&late.build()), Some(late));
+    }
+
+    #[test]
+    fn should_replace_bound_nonce() {
+        let rebind = |predicate: Option<Predicate>| {
+            Predicate::bind_nonce(
+                Some(Predicate::bind_nonce(predicate, maker(), 3)),
+                maker(),
+                4,
+            )
+        };
+
+        assert_eq!(rebind(None), Predicate::bind_nonce(None, maker(), 4));
+        assert_eq!(
+            rebind(Some(Predicate::TimestampBelow(1673548200))),
+            Predicate::TimestampBelowAndNonceEquals {
+                time: 1673548200,
+                nonce: 4,
+                account: maker(),
+            }
+        );
+        assert_eq!(
+            rebind(Some(Predicate::Not(Box::new(Predicate::TimestampBelow(
+                100
+            ))))),
+            Predicate::And(vec![
+                Predicate::Not(Box::new(Predicate::TimestampBelow(100))),
+                Predicate::NonceEquals {
+                    account: maker(),
+                    nonce: U256::from(4)
+                }
+            ])
+        );
+
+        // nonce of another account is a separate check
+        let other = Predicate::bind_nonce(
+            Some(Predicate::bind_nonce(None, Address::zero(), 3)),
+            maker(),
+            4,
+        );
+
+        assert_eq!(other.nonce(&Address::zero()), Some(U256::from(3)));
+        assert_eq!(other.nonce(&maker()), Some(U256::from(4)));
+    }
+
+    #[test]
+    fn should_compute_expiration() {
+        let nonce = Predicate::NonceEquals {
+            account: maker(),
+            nonce: U256::from(1),
+        };
+
+        assert_eq!(
+            Predicate::And(vec![
+                Predicate::TimestampBelow(200),
+                nonce.clone(),
+                Predicate::TimestampBelow(100)
+            ])
+            .expiration(),
+            Some(100)
+        );
+        assert_eq!(
+            Predicate::Or(vec![
+                Predicate::TimestampBelow(200),
+                Predicate::TimestampBelow(100)
+            ])
+            .expiration(),
+            Some(200)
+        );
+        assert_eq!(
+            Predicate::Or(vec![Predicate::TimestampBelow(200), nonce]).expiration(),
+            None
+        );
+    }
+}
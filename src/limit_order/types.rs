@@ -1,8 +1,13 @@
-use ethers::types::{Address, Bytes, U256};
+use ethers::{
+    abi::{decode, encode, ParamType, Token},
+    types::{Address, Bytes, U256},
+};
 use serde_json::json;
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, str::FromStr};
 use struct_field_names_as_array::FieldNamesAsArray;
 
+use crate::utils::parse_u256;
+
 #[derive(FieldNamesAsArray, Debug, Clone, PartialEq)]
 pub struct LimitOrderV3Struct {
     pub salt: U256,
@@ -50,6 +55,102 @@ impl ToBtreeMap for LimitOrderV3Struct {
     }
 }
 
+impl LimitOrderV3Struct {
+    // same field names as in typed data message and in 1inch api responses
+    pub fn from_json(value: &serde_json::Value) -> Result<Self, String> {
+        let field = |name: &str| -> Result<&str, String> {
+            match value.get(name) {
+                Some(serde_json::Value::String(s)) => Ok(s.as_str()),
+                Some(_) => Err(format!("Field {} should be a string", name)),
+                None => Err(format!("Missing field {}", name)),
+            }
+        };
+        let address = |name: &str| -> Result<Address, String> {
+            Address::from_str(field(name)?).map_err(|e| format!("Invalid {}: {}", name, e))
+        };
+        let number = |name: &str| -> Result<U256, String> { parse_u256(field(name)?) };
+
+        Ok(Self {
+            salt: number("salt")?,
+            maker_asset: address("makerAsset")?,
+            taker_asset: address("takerAsset")?,
+            maker: address("maker")?,
+            receiver: address("receiver")?,
+            allowed_sender: address("allowedSender")?,
+            making_amount: number("makingAmount")?,
+            taking_amount: number("takingAmount")?,
+            offsets: number("offsets")?,
+            interactions: Bytes::from_str(field("interactions")?)
+                .map_err(|e| format!("Invalid interactions: {}", e))?,
+        })
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        json!(self.to_btree_map())
+    }
+
+    pub fn param_type() -> ParamType {
+        ParamType::Tuple(vec![
+            ParamType::Uint(256),
+            ParamType::Address,
+            ParamType::Address,
+            ParamType::Address,
+            ParamType::Address,
+            ParamType::Address,
+            ParamType::Uint(256),
+            ParamType::Uint(256),
+            ParamType::Uint(256),
+            ParamType::Bytes,
+        ])
+    }
+
+    pub fn to_token(&self) -> Token {
+        Token::Tuple(vec![
+            Token::Uint(self.salt),
+            Token::Address(self.maker_asset),
+            Token::Address(self.taker_asset),
+            Token::Address(self.maker),
+            Token::Address(self.receiver),
+            Token::Address(self.allowed_sender),
+            Token::Uint(self.making_amount),
+            Token::Uint(self.taking_amount),
+            Token::Uint(self.offsets),
+            Token::Bytes(self.interactions.to_vec()),
+        ])
+    }
+
+    pub fn from_token(token: Token) -> Result<Self, String> {
+        let invalid = || "Invalid order tuple".to_string();
+
+        let mut fields = token.into_tuple().ok_or_else(invalid)?.into_iter();
+        let mut next = || fields.next().ok_or_else(invalid);
+
+        Ok(Self {
+            salt: next()?.into_uint().ok_or_else(invalid)?,
+            maker_asset: next()?.into_address().ok_or_else(invalid)?,
+            taker_asset: next()?.into_address().ok_or_else(invalid)?,
+            maker: next()?.into_address().ok_or_else(invalid)?,
+            receiver: next()?.into_address().ok_or_else(invalid)?,
+            allowed_sender: next()?.into_address().ok_or_else(invalid)?,
+            making_amount: next()?.into_uint().ok_or_else(invalid)?,
+            taking_amount: next()?.into_uint().ok_or_else(invalid)?,
+            offsets: next()?.into_uint().ok_or_else(invalid)?,
+            interactions: next()?.into_bytes().ok_or_else(invalid)?.into(),
+        })
+    }
+
+    // abi encoded order tuple, as it's passed to the router
+    pub fn encode_abi(&self) -> Bytes {
+        encode(&[self.to_token()]).into()
+    }
+
+    pub fn decode_abi(data: &[u8]) -> Result<Self, String> {
+        let mut tokens = decode(&[Self::param_type()], data).map_err(|e| e.to_string())?;
+
+        Self::from_token(tokens.remove(0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -104,4 +205,60 @@ mod tests {
 
         assert_eq!(map, expected_map);
     }
+
+    fn order() -> LimitOrderV3Struct {
+        LimitOrderV3Struct {
+            salt: U256::from(1673549418040u64),
+            maker_asset: Address::from_str("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2").unwrap(),
+            taker_asset: Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap(),
+            maker: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+            receiver: Address::zero(),
+            allowed_sender: Address::zero(),
+            making_amount: U256::from(1000000000000000000u64),
+            taking_amount: U256::from(1420000000),
+            offsets: U256::from(7) << 224,
+            interactions: Bytes::from_str("0x3a7f2c8b1d4e6f").unwrap(),
+        }
+    }
+
+    #[test]
+    fn should_parse_order_from_json() {
+        let value = json!({
+            "salt": "1673549418040",
+            "makerAsset": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "takerAsset": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            "maker": "0x00000000219ab540356cbb839cbe05303d7705fa",
+            "receiver": "0x0000000000000000000000000000000000000000",
+            "allowedSender": "0x0000000000000000000000000000000000000000",
+            "makingAmount": "1000000000000000000",
+            "takingAmount": "0x54a37b00",
+            "offsets": "188719626670054478562669105609137414715460010957784007367725271744512",
+            "interactions": "0x3a7f2c8b1d4e6f"
+        });
+
+        assert_eq!(LimitOrderV3Struct::from_json(&value), Ok(order()));
+        assert_eq!(
+            LimitOrderV3Struct::from_json(&order().to_json()),
+            Ok(order())
+        );
+    }
+
+    #[test]
+    fn should_fail_to_parse_order_without_field() {
+        let mut value = order().to_json();
+        value.as_object_mut().unwrap().remove("maker");
+
+        assert_eq!(
+            LimitOrderV3Struct::from_json(&value),
+            Err("Missing field maker".to_string())
+        );
+    }
+
+    #[test]
+    fn should_encode_and_decode_abi() {
+        let encoded = order().encode_abi();
+
+        assert_eq!(LimitOrderV3Struct::decode_abi(&encoded), Ok(order()));
+        assert!(LimitOrderV3Struct::decode_abi(&encoded[..64]).is_err());
+    }
 }
@@ -0,0 +1,20 @@
+// https://github.com/1inch/limit-order-protocol/blob/master/contracts/libraries/MakerTraitsLib.sol
+pub const NO_PARTIAL_FILLS_FLAG: usize = 255;
+pub const ALLOW_MULTIPLE_FILLS_FLAG: usize = 254;
+pub const PRE_INTERACTION_CALL_FLAG: usize = 252;
+pub const POST_INTERACTION_CALL_FLAG: usize = 251;
+pub const NEED_CHECK_EPOCH_MANAGER_FLAG: usize = 250;
+pub const HAS_EXTENSION_FLAG: usize = 249;
+pub const USE_PERMIT2_FLAG: usize = 248;
+pub const UNWRAP_WETH_FLAG: usize = 247;
+
+pub const ALLOWED_SENDER_OFFSET: usize = 0; // allowedSender 0-79
+pub const ALLOWED_SENDER_BITS: usize = 80;
+pub const EXPIRATION_OFFSET: usize = 80; // expiration 80-119
+pub const EXPIRATION_BITS: usize = 40;
+pub const NONCE_OR_EPOCH_OFFSET: usize = 120; // nonceOrEpoch 120-159
+pub const NONCE_OR_EPOCH_BITS: usize = 40;
+pub const SERIES_OFFSET: usize = 160; // series 160-199
+pub const SERIES_BITS: usize = 40;
+
+pub const EXTENSION_HASH_BITS: usize = 160;
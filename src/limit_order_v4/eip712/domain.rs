@@ -0,0 +1,22 @@
+use ethers::types::transaction::eip712::Eip712DomainType;
+use serde_json::json;
+
+pub fn order_type() -> Vec<Eip712DomainType> {
+    let domain_types_json = json!([
+        {"name": "salt", "type": "uint256"},
+        {"name": "maker", "type": "address"},
+        {"name": "receiver", "type": "address"},
+        {"name": "makerAsset", "type": "address"},
+        {"name": "takerAsset", "type": "address"},
+        {"name": "makingAmount", "type": "uint256"},
+        {"name": "takingAmount", "type": "uint256"},
+        {"name": "makerTraits", "type": "uint256"}
+    ]);
+
+    let domain_types: Vec<Eip712DomainType> = serde_json::from_value(domain_types_json).unwrap();
+
+    domain_types
+}
+
+pub const LIMIT_ORDER_V4_TYPE_DATA_NAME: &str = "1inch Aggregation Router";
+pub const LIMIT_ORDER_V4_TYPE_DATA_VERSION: &str = "6";
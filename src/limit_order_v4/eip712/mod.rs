@@ -0,0 +1,2 @@
+pub mod domain;
+pub mod order_typed_data_builder;
@@ -0,0 +1,50 @@
+use ethers::types::transaction::eip712::{EIP712Domain, Eip712DomainType, TypedData};
+use ethers::types::{H160, U256};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::constants::ONE_INCH_ROUTER_V6;
+use crate::limit_order::eip712::domain::eip712_domain_type;
+use crate::limit_order::types::ToBtreeMap;
+use crate::limit_order_v4::types::LimitOrderV4Struct;
+
+use super::domain::{order_type, LIMIT_ORDER_V4_TYPE_DATA_NAME, LIMIT_ORDER_V4_TYPE_DATA_VERSION};
+
+pub fn build_order_data(
+    chain_id: &U256,
+    verifying_contract: &H160,
+    name: &str,
+    version: &str,
+    order: &LimitOrderV4Struct,
+) -> TypedData {
+    let domain = EIP712Domain {
+        chain_id: Some(*chain_id),
+        verifying_contract: Some(verifying_contract.to_owned()),
+        name: Some(name.to_string()),
+        version: Some(version.to_string()),
+        salt: None,
+    };
+    let types: BTreeMap<String, Vec<Eip712DomainType>> = BTreeMap::from([
+        ("EIP712Domain".to_string(), eip712_domain_type()),
+        ("Order".to_string(), order_type()),
+    ]);
+
+    let message = order.to_btree_map();
+
+    TypedData {
+        primary_type: "Order".to_string(),
+        types,
+        domain,
+        message,
+    }
+}
+
+pub fn get_limit_order_v4_domain(chain_id: &U256) -> EIP712Domain {
+    EIP712Domain {
+        chain_id: Some(*chain_id),
+        verifying_contract: Some(H160::from_str(ONE_INCH_ROUTER_V6).unwrap()),
+        name: Some(LIMIT_ORDER_V4_TYPE_DATA_NAME.to_string()),
+        version: Some(LIMIT_ORDER_V4_TYPE_DATA_VERSION.to_string()),
+        salt: None,
+    }
+}
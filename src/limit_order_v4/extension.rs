@@ -0,0 +1,194 @@
+use ethers::{
+    types::{Bytes, U256},
+    utils::keccak256,
+};
+
+use crate::{
+    limit_order::parser::{get_offset_for_interaction, parse_interaction_field},
+    utils::{cumsum, Maskn},
+};
+
+use super::constants::EXTENSION_HASH_BITS;
+
+const OFFSETS_LENGTH: usize = 32;
+const OFFSETS_FIELDS_COUNT: u8 = 8;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Extension {
+    pub maker_asset_suffix: Bytes,
+    pub taker_asset_suffix: Bytes,
+    pub making_amount_data: Bytes,
+    pub taking_amount_data: Bytes,
+    pub predicate: Bytes,
+    pub maker_permit: Bytes,
+    pub pre_interaction: Bytes,
+    pub post_interaction: Bytes,
+    pub custom_data: Bytes,
+}
+
+impl Extension {
+    pub fn decode(extension: &Bytes) -> Result<Self, String> {
+        if extension.is_empty() {
+            return Ok(Self::default());
+        }
+
+        if extension.len() < OFFSETS_LENGTH {
+            return Err("Wrong extension length".to_string());
+        }
+
+        let offsets = U256::from(&extension[0..OFFSETS_LENGTH]);
+        let data = Bytes::from(extension[OFFSETS_LENGTH..].to_vec());
+
+        let mut previous_end = 0;
+
+        for index in 0..OFFSETS_FIELDS_COUNT {
+            let (_, end) = get_offset_for_interaction(&offsets, index);
+
+            if end < previous_end || end > data.len() {
+                return Err("Wrong extension offsets".to_string());
+            }
+
+            previous_end = end;
+        }
+
+        let field = |index: u8| parse_interaction_field(&offsets, &data, index);
+
+        Ok(Self {
+            maker_asset_suffix: field(0),
+            taker_asset_suffix: field(1),
+            making_amount_data: field(2),
+            taking_amount_data: field(3),
+            predicate: field(4),
+            maker_permit: field(5),
+            pre_interaction: field(6),
+            post_interaction: field(7),
+            custom_data: Bytes::from(data[previous_end..].to_vec()),
+        })
+    }
+
+    pub fn build(&self) -> Bytes {
+        let fields = self.offset_fields();
+
+        if fields.iter().all(|f| f.is_empty()) && self.custom_data.is_empty() {
+            return Bytes::default();
+        }
+
+        let lengths: Vec<usize> = fields.iter().map(|x| x.len()).collect();
+        let sums = cumsum(&lengths);
+        let offsets = sums.iter().enumerate().fold(U256::zero(), |acc, (i, x)| {
+            acc + (U256::from(*x) << (32 * i))
+        });
+
+        let mut offsets_bytes = [0u8; OFFSETS_LENGTH];
+        offsets.to_big_endian(&mut offsets_bytes);
+
+        [
+            offsets_bytes.to_vec(),
+            fields.concat(),
+            self.custom_data.to_vec(),
+        ]
+        .concat()
+        .into()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.build().is_empty()
+    }
+
+    pub fn keccak256(&self) -> U256 {
+        U256::from(keccak256(self.build()))
+    }
+
+    // lower 160 bits of the order salt must be equal to the same bits of the extension hash
+    pub fn salt_bits(&self) -> U256 {
+        self.keccak256().maskn(EXTENSION_HASH_BITS)
+    }
+
+    fn offset_fields(&self) -> Vec<Vec<u8>> {
+        vec![
+            self.maker_asset_suffix.to_vec(),
+            self.taker_asset_suffix.to_vec(),
+            self.making_amount_data.to_vec(),
+            self.taking_amount_data.to_vec(),
+            self.predicate.to_vec(),
+            self.maker_permit.to_vec(),
+            self.pre_interaction.to_vec(),
+            self.post_interaction.to_vec(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::types::Bytes;
+    use pretty_assertions::assert_eq;
+
+    use super::Extension;
+
+    #[test]
+    fn should_build_empty_extension() {
+        assert_eq!(Extension::default().build(), Bytes::default());
+        assert_eq!(
+            Extension::decode(&Bytes::default()),
+            Ok(Extension::default())
+        );
+    }
+
+    #[test]
+    fn should_build_extension() {
+        let extension = Extension {
+            predicate: Bytes::from_str(
+                "0x63592c2b0000000000000000000000000000000000000000000000000000000063c0566a",
+            )
+            .unwrap(),
+            post_interaction: Bytes::from_str("0x08b067ad41e45babe5bbb52fc2fe7f692f628b06")
+                .unwrap(),
+            custom_data: Bytes::from_str("0xdeadbeef").unwrap(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            extension.build(),
+            Bytes::from_str("0x000000380000002400000024000000240000000000000000000000000000000063592c2b0000000000000000000000000000000000000000000000000000000063c0566a08b067ad41e45babe5bbb52fc2fe7f692f628b06deadbeef").unwrap()
+        );
+    }
+
+    #[test]
+    fn should_decode_extension() {
+        let extension = Extension {
+            maker_asset_suffix: Bytes::from_str("0x01").unwrap(),
+            making_amount_data: Bytes::from_str("0x0203").unwrap(),
+            maker_permit: Bytes::from_str("0x040506").unwrap(),
+            pre_interaction: Bytes::from_str("0x07").unwrap(),
+            post_interaction: Bytes::from_str("0x08b067ad41e45babe5bbb52fc2fe7f692f628b06")
+                .unwrap(),
+            custom_data: Bytes::from_str("0xdeadbeef").unwrap(),
+            ..Default::default()
+        };
+
+        assert_eq!(Extension::decode(&extension.build()), Ok(extension));
+    }
+
+    #[test]
+    fn should_refuse_wrong_extension() {
+        assert_eq!(
+            Extension::decode(&Bytes::from_str("0x0102").unwrap()),
+            Err("Wrong extension length".to_string())
+        );
+
+        let mut extension = Extension {
+            predicate: Bytes::from_str("0x0102").unwrap(),
+            ..Default::default()
+        }
+        .build()
+        .to_vec();
+        extension.pop();
+
+        assert_eq!(
+            Extension::decode(&extension.into()),
+            Err("Wrong extension offsets".to_string())
+        );
+    }
+}
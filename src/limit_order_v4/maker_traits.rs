@@ -0,0 +1,253 @@
+use ethers::types::{Address, U256};
+
+use crate::utils::Maskn;
+
+use super::constants::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MakerTraits {
+    value: U256,
+}
+
+impl MakerTraits {
+    pub fn decode(value: &U256) -> Self {
+        Self { value: *value }
+    }
+
+    pub fn build(&self) -> U256 {
+        self.value
+    }
+
+    // low 80 bits of the address, zero when any sender is allowed
+    pub fn allowed_sender(&self) -> U256 {
+        self.get_mask(ALLOWED_SENDER_OFFSET, ALLOWED_SENDER_BITS)
+    }
+
+    pub fn is_allowed_sender(&self, sender: &Address) -> bool {
+        let allowed_sender = self.allowed_sender();
+
+        allowed_sender.is_zero()
+            || allowed_sender == U256::from(sender.as_bytes()).maskn(ALLOWED_SENDER_BITS)
+    }
+
+    pub fn with_allowed_sender(self, sender: &Address) -> Self {
+        self.set_mask(
+            ALLOWED_SENDER_OFFSET,
+            ALLOWED_SENDER_BITS,
+            U256::from(sender.as_bytes()),
+        )
+    }
+
+    // unix timestamp, None when the order never expires
+    pub fn expiration(&self) -> Option<u64> {
+        let expiration = self.get_mask(EXPIRATION_OFFSET, EXPIRATION_BITS);
+
+        if expiration.is_zero() {
+            None
+        } else {
+            Some(expiration.as_u64())
+        }
+    }
+
+    pub fn with_expiration(self, expiration: u64) -> Self {
+        self.set_mask(EXPIRATION_OFFSET, EXPIRATION_BITS, U256::from(expiration))
+    }
+
+    pub fn nonce_or_epoch(&self) -> u64 {
+        self.get_mask(NONCE_OR_EPOCH_OFFSET, NONCE_OR_EPOCH_BITS)
+            .as_u64()
+    }
+
+    pub fn with_nonce(self, nonce: u64) -> Self {
+        self.set_mask(
+            NONCE_OR_EPOCH_OFFSET,
+            NONCE_OR_EPOCH_BITS,
+            U256::from(nonce),
+        )
+    }
+
+    pub fn series(&self) -> u64 {
+        self.get_mask(SERIES_OFFSET, SERIES_BITS).as_u64()
+    }
+
+    pub fn with_epoch(self, series: u64, epoch: u64) -> Self {
+        self.set_mask(SERIES_OFFSET, SERIES_BITS, U256::from(series))
+            .set_mask(
+                NONCE_OR_EPOCH_OFFSET,
+                NONCE_OR_EPOCH_BITS,
+                U256::from(epoch),
+            )
+            .set_bit(NEED_CHECK_EPOCH_MANAGER_FLAG, true)
+    }
+
+    pub fn is_epoch_manager_enabled(&self) -> bool {
+        self.get_bit(NEED_CHECK_EPOCH_MANAGER_FLAG)
+    }
+
+    pub fn is_partial_fill_allowed(&self) -> bool {
+        !self.get_bit(NO_PARTIAL_FILLS_FLAG)
+    }
+
+    pub fn allow_partial_fills(self) -> Self {
+        self.set_bit(NO_PARTIAL_FILLS_FLAG, false)
+    }
+
+    pub fn disable_partial_fills(self) -> Self {
+        self.set_bit(NO_PARTIAL_FILLS_FLAG, true)
+    }
+
+    pub fn is_multiple_fills_allowed(&self) -> bool {
+        self.get_bit(ALLOW_MULTIPLE_FILLS_FLAG)
+    }
+
+    pub fn allow_multiple_fills(self) -> Self {
+        self.set_bit(ALLOW_MULTIPLE_FILLS_FLAG, true)
+    }
+
+    pub fn disable_multiple_fills(self) -> Self {
+        self.set_bit(ALLOW_MULTIPLE_FILLS_FLAG, false)
+    }
+
+    pub fn has_pre_interaction(&self) -> bool {
+        self.get_bit(PRE_INTERACTION_CALL_FLAG)
+    }
+
+    pub fn enable_pre_interaction(self) -> Self {
+        self.set_bit(PRE_INTERACTION_CALL_FLAG, true)
+    }
+
+    pub fn has_post_interaction(&self) -> bool {
+        self.get_bit(POST_INTERACTION_CALL_FLAG)
+    }
+
+    pub fn enable_post_interaction(self) -> Self {
+        self.set_bit(POST_INTERACTION_CALL_FLAG, true)
+    }
+
+    pub fn has_extension(&self) -> bool {
+        self.get_bit(HAS_EXTENSION_FLAG)
+    }
+
+    pub fn with_extension(self) -> Self {
+        self.set_bit(HAS_EXTENSION_FLAG, true)
+    }
+
+    pub fn is_permit2(&self) -> bool {
+        self.get_bit(USE_PERMIT2_FLAG)
+    }
+
+    pub fn enable_permit2(self) -> Self {
+        self.set_bit(USE_PERMIT2_FLAG, true)
+    }
+
+    pub fn is_native_unwrap_enabled(&self) -> bool {
+        self.get_bit(UNWRAP_WETH_FLAG)
+    }
+
+    pub fn enable_native_unwrap(self) -> Self {
+        self.set_bit(UNWRAP_WETH_FLAG, true)
+    }
+
+    fn get_bit(&self, bit: usize) -> bool {
+        self.value.bit(bit)
+    }
+
+    fn set_bit(self, bit: usize, value: bool) -> Self {
+        let mask = U256::one() << bit;
+
+        Self {
+            value: if value {
+                self.value | mask
+            } else {
+                self.value & !mask
+            },
+        }
+    }
+
+    fn get_mask(&self, offset: usize, bits: usize) -> U256 {
+        (self.value >> offset).maskn(bits)
+    }
+
+    fn set_mask(self, offset: usize, bits: usize, value: U256) -> Self {
+        let mask = ((U256::one() << bits) - 1) << offset;
+
+        Self {
+            value: (self.value & !mask) | ((value.maskn(bits) << offset) & mask),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::types::{Address, U256};
+    use pretty_assertions::assert_eq;
+
+    use super::MakerTraits;
+
+    #[test]
+    fn should_have_defaults_of_protocol() {
+        let traits = MakerTraits::default();
+
+        assert_eq!(traits.build(), U256::zero());
+        assert!(traits.is_partial_fill_allowed());
+        assert!(!traits.is_multiple_fills_allowed());
+        assert_eq!(traits.expiration(), None);
+        assert!(traits.is_allowed_sender(
+            &Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap()
+        ));
+    }
+
+    #[test]
+    fn should_build_maker_traits() {
+        let sender = Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap();
+
+        let traits = MakerTraits::default()
+            .with_allowed_sender(&sender)
+            .with_expiration(1673549418)
+            .with_nonce(7)
+            .allow_multiple_fills()
+            .disable_partial_fills()
+            .enable_post_interaction()
+            .with_extension();
+
+        assert_eq!(
+            traits.build(),
+            U256::from_str("0xca000000000000000000000000000000070063c0566abb839cbe05303d7705fa")
+                .unwrap()
+        );
+        assert_eq!(traits.expiration(), Some(1673549418));
+        assert_eq!(traits.nonce_or_epoch(), 7);
+        assert!(!traits.is_partial_fill_allowed());
+        assert!(traits.is_multiple_fills_allowed());
+        assert!(!traits.has_pre_interaction());
+        assert!(traits.has_post_interaction());
+        assert!(traits.has_extension());
+        assert!(traits.is_allowed_sender(&sender));
+        assert!(!traits.is_allowed_sender(&Address::zero()));
+    }
+
+    #[test]
+    fn should_set_epoch_and_series() {
+        let traits = MakerTraits::default()
+            .with_epoch(3, 42)
+            .with_expiration(100);
+
+        assert_eq!(traits.series(), 3);
+        assert_eq!(traits.nonce_or_epoch(), 42);
+        assert!(traits.is_epoch_manager_enabled());
+        assert_eq!(traits.expiration(), Some(100));
+    }
+
+    #[test]
+    fn should_toggle_flags_back() {
+        let traits = MakerTraits::default()
+            .disable_partial_fills()
+            .allow_multiple_fills()
+            .allow_partial_fills()
+            .disable_multiple_fills();
+
+        assert_eq!(traits, MakerTraits::default());
+    }
+}
@@ -0,0 +1,281 @@
+use ethers::types::{
+    transaction::eip712::{EIP712Domain, TypedData},
+    Address, Bytes, U256,
+};
+
+use crate::{
+    constants::ZERO_ADDRESS,
+    limit_order::{eip712::order_typed_data_builder::get_order_hash, OrderInfoData},
+    salt::build_salt,
+    utils::Maskn,
+};
+
+use self::{
+    constants::EXTENSION_HASH_BITS,
+    eip712::order_typed_data_builder::{build_order_data, get_limit_order_v4_domain},
+    extension::Extension,
+    maker_traits::MakerTraits,
+    types::LimitOrderV4Struct,
+};
+
+pub mod constants;
+pub mod eip712;
+pub mod extension;
+pub mod maker_traits;
+pub mod types;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LimitOrderV4 {
+    salt: U256,
+    maker: Address,
+    receiver: Address,
+    maker_asset: Address,
+    taker_asset: Address,
+    making_amount: U256,
+    taking_amount: U256,
+    maker_traits: MakerTraits,
+    extension: Extension,
+}
+
+impl LimitOrderV4 {
+    pub fn new(
+        order_info: &OrderInfoData,
+        maker_traits: MakerTraits,
+        extension: Option<&Extension>,
+    ) -> Self {
+        let extension = extension.cloned().unwrap_or_default();
+        let base_salt = order_info.salt.unwrap_or_else(build_salt);
+
+        let mut maker_traits = maker_traits;
+
+        if let Some(allowed_sender) = order_info.allowed_sender {
+            maker_traits = maker_traits.with_allowed_sender(&allowed_sender);
+        }
+
+        let salt = if extension.is_empty() {
+            base_salt
+        } else {
+            maker_traits = maker_traits.with_extension();
+
+            if !extension.pre_interaction.is_empty() {
+                maker_traits = maker_traits.enable_pre_interaction();
+            }
+
+            if !extension.post_interaction.is_empty() {
+                maker_traits = maker_traits.enable_post_interaction();
+            }
+
+            (base_salt.maskn(256 - EXTENSION_HASH_BITS) << EXTENSION_HASH_BITS)
+                | extension.salt_bits()
+        };
+
+        LimitOrderV4 {
+            salt,
+            maker: order_info.maker,
+            receiver: order_info.receiver.unwrap_or(ZERO_ADDRESS),
+            maker_asset: order_info.maker_asset,
+            taker_asset: order_info.taker_asset,
+            making_amount: order_info.making_amount,
+            taking_amount: order_info.taking_amount,
+            maker_traits,
+            extension,
+        }
+    }
+
+    pub fn decode(r#struct: &LimitOrderV4Struct, extension: &Bytes) -> Result<Self, String> {
+        let maker_traits = MakerTraits::decode(&r#struct.maker_traits);
+        let extension = Extension::decode(extension)?;
+
+        if maker_traits.has_extension() == extension.is_empty() {
+            return Err("has_extension flag doesn't match extension".to_string());
+        }
+
+        if maker_traits.has_extension()
+            && r#struct.salt.maskn(EXTENSION_HASH_BITS) != extension.salt_bits()
+        {
+            return Err("salt doesn't match extension hash".to_string());
+        }
+
+        Ok(LimitOrderV4 {
+            salt: r#struct.salt,
+            maker: r#struct.maker,
+            receiver: r#struct.receiver,
+            maker_asset: r#struct.maker_asset,
+            taker_asset: r#struct.taker_asset,
+            making_amount: r#struct.making_amount,
+            taking_amount: r#struct.taking_amount,
+            maker_traits,
+            extension,
+        })
+    }
+
+    pub fn build(&self) -> LimitOrderV4Struct {
+        LimitOrderV4Struct {
+            salt: self.salt,
+            maker: self.maker,
+            receiver: self.receiver,
+            maker_asset: self.maker_asset,
+            taker_asset: self.taker_asset,
+            making_amount: self.making_amount,
+            taking_amount: self.taking_amount,
+            maker_traits: self.maker_traits.build(),
+        }
+    }
+
+    pub fn salt(&self) -> U256 {
+        self.salt
+    }
+
+    pub fn maker_traits(&self) -> MakerTraits {
+        self.maker_traits
+    }
+
+    pub fn extension(&self) -> &Extension {
+        &self.extension
+    }
+
+    pub fn get_typed_data(&self, domain: Option<&EIP712Domain>) -> TypedData {
+        let domain = domain
+            .cloned()
+            .unwrap_or_else(|| get_limit_order_v4_domain(&U256::from(1)));
+
+        build_order_data(
+            &domain.chain_id.unwrap(),
+            &domain.verifying_contract.unwrap(),
+            &domain.name.unwrap(),
+            &domain.version.unwrap(),
+            &self.build(),
+        )
+    }
+
+    pub fn get_order_hash(&self, domain: Option<&EIP712Domain>) -> String {
+        get_order_hash(self.get_typed_data(domain))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::{
+        abi::{encode, AbiEncode, Token},
+        types::{Address, Bytes, U256},
+        utils::keccak256,
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::limit_order::{eip712::order_typed_data_builder::domain_separator, OrderInfoData};
+
+    use super::{extension::Extension, maker_traits::MakerTraits, LimitOrderV4};
+
+    fn order_info() -> OrderInfoData {
+        OrderInfoData {
+            maker_asset: Address::from_str("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2").unwrap(),
+            taker_asset: Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap(),
+            making_amount: U256::from(1000000000000000000u64),
+            taking_amount: U256::from(1420000000),
+            maker: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+            salt: Some(U256::from(1673549418040u64)),
+            allowed_sender: None,
+            receiver: None,
+        }
+    }
+
+    #[test]
+    fn should_create_limit_order_without_extension() {
+        let order = LimitOrderV4::new(&order_info(), MakerTraits::default(), None);
+
+        let built = order.build();
+
+        assert_eq!(built.salt, U256::from(1673549418040u64));
+        assert_eq!(built.maker_traits, U256::zero());
+        assert_eq!(built.receiver, Address::zero());
+        assert_eq!(order.extension().build(), Bytes::default());
+    }
+
+    #[test]
+    fn should_put_extension_hash_into_salt() {
+        let extension = Extension {
+            post_interaction: Bytes::from_str("0x08b067ad41e45babe5bbb52fc2fe7f692f628b06")
+                .unwrap(),
+            ..Default::default()
+        };
+
+        let order = LimitOrderV4::new(
+            &order_info(),
+            MakerTraits::default().allow_multiple_fills(),
+            Some(&extension),
+        );
+
+        let built = order.build();
+        let traits = MakerTraits::decode(&built.maker_traits);
+
+        assert_eq!(built.salt >> 160, U256::from(1673549418040u64));
+        assert_eq!(
+            built.salt & ((U256::one() << 160) - 1),
+            U256::from(keccak256(extension.build())) & ((U256::one() << 160) - 1)
+        );
+        assert!(traits.has_extension());
+        assert!(traits.has_post_interaction());
+        assert!(!traits.has_pre_interaction());
+        assert!(traits.is_multiple_fills_allowed());
+
+        assert_eq!(LimitOrderV4::decode(&built, &extension.build()), Ok(order));
+    }
+
+    #[test]
+    fn should_fail_to_decode_order_with_foreign_extension() {
+        let extension = Extension {
+            predicate: Bytes::from_str("0x01").unwrap(),
+            ..Default::default()
+        };
+        let order = LimitOrderV4::new(&order_info(), MakerTraits::default(), Some(&extension));
+
+        let foreign = Extension {
+            predicate: Bytes::from_str("0x02").unwrap(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            LimitOrderV4::decode(&order.build(), &foreign.build()),
+            Err("salt doesn't match extension hash".to_string())
+        );
+    }
+
+    #[test]
+    fn should_get_limit_order_hash() {
+        let order = LimitOrderV4::new(
+            &order_info(),
+            MakerTraits::default().with_expiration(1673549418),
+            None,
+        );
+        let built = order.build();
+
+        let type_hash = keccak256(
+            "Order(uint256 salt,address maker,address receiver,address makerAsset,address takerAsset,uint256 makingAmount,uint256 takingAmount,uint256 makerTraits)",
+        );
+        let struct_hash = keccak256(encode(&[
+            Token::FixedBytes(type_hash.to_vec()),
+            Token::Uint(built.salt),
+            Token::Address(built.maker),
+            Token::Address(built.receiver),
+            Token::Address(built.maker_asset),
+            Token::Address(built.taker_asset),
+            Token::Uint(built.making_amount),
+            Token::Uint(built.taking_amount),
+            Token::Uint(built.maker_traits),
+        ]));
+        let separator = Bytes::from_str(&domain_separator(
+            "1inch Aggregation Router",
+            "6",
+            &U256::from(1),
+            "0x111111125421ca6dc452d289314280a0f8842a65",
+        ))
+        .unwrap();
+
+        let expected =
+            keccak256([&[0x19, 0x01], separator.as_ref(), struct_hash.as_ref()].concat());
+
+        assert_eq!(order.get_order_hash(None), expected.encode_hex());
+    }
+}
@@ -0,0 +1,48 @@
+use ethers::types::{Address, U256};
+use serde_json::json;
+use std::collections::BTreeMap;
+use struct_field_names_as_array::FieldNamesAsArray;
+
+use crate::limit_order::types::ToBtreeMap;
+
+#[derive(FieldNamesAsArray, Debug, Clone, PartialEq)]
+pub struct LimitOrderV4Struct {
+    pub salt: U256,
+    pub maker: Address,
+    pub receiver: Address,
+    pub maker_asset: Address,
+    pub taker_asset: Address,
+    pub making_amount: U256,
+    pub taking_amount: U256,
+    pub maker_traits: U256,
+}
+
+impl ToBtreeMap for LimitOrderV4Struct {
+    fn to_btree_map(&self) -> BTreeMap<String, serde_json::Value> {
+        let mut map = BTreeMap::new();
+
+        map.insert("salt".to_string(), json!(self.salt.to_string()));
+        map.insert("maker".to_string(), json!(self.maker));
+        map.insert("receiver".to_string(), json!(self.receiver));
+        map.insert("makerAsset".to_string(), json!(self.maker_asset));
+        map.insert("takerAsset".to_string(), json!(self.taker_asset));
+        map.insert(
+            "makingAmount".to_string(),
+            json!(self.making_amount.to_string()),
+        );
+        map.insert(
+            "takingAmount".to_string(),
+            json!(self.taking_amount.to_string()),
+        );
+        map.insert(
+            "makerTraits".to_string(),
+            json!(self.maker_traits.to_string()),
+        );
+
+        if map.len() != LimitOrderV4Struct::FIELD_NAMES_AS_ARRAY.len() {
+            panic!("Not all fields were serialized");
+        }
+
+        map
+    }
+}
@@ -1,3 +1,12 @@
+use clap::Parser;
+
+mod cli;
+
 fn main() {
-    println!("Hello, world!");
+    let cli = cli::Cli::parse();
+
+    if let Err(e) = cli.run() {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
 }
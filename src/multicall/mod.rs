@@ -0,0 +1,146 @@
+pub mod types;
+
+use ethers::{
+    abi::{decode, encode, ParamType, Token},
+    providers::Middleware,
+    types::{transaction::eip2718::TypedTransaction, Address, Bytes, TransactionRequest},
+    utils::id,
+};
+
+use types::{Call3, CallResult};
+
+// https://github.com/mds1/multicall/blob/main/src/Multicall3.sol
+pub const AGGREGATE3: &str = "aggregate3((address,bool,bytes)[])";
+
+pub fn encode_aggregate3(calls: &[Call3]) -> Bytes {
+    let calls = calls
+        .iter()
+        .map(|call| {
+            Token::Tuple(vec![
+                Token::Address(call.target),
+                Token::Bool(call.allow_failure),
+                Token::Bytes(call.call_data.to_vec()),
+            ])
+        })
+        .collect();
+
+    [id(AGGREGATE3).to_vec(), encode(&[Token::Array(calls)])]
+        .concat()
+        .into()
+}
+
+pub fn decode_aggregate3(data: &[u8]) -> Result<Vec<CallResult>, String> {
+    let result_type = ParamType::Array(Box::new(ParamType::Tuple(vec![
+        ParamType::Bool,
+        ParamType::Bytes,
+    ])));
+
+    let results = decode(&[result_type], data)
+        .map_err(|e| e.to_string())?
+        .remove(0)
+        .into_array()
+        .unwrap();
+
+    Ok(results
+        .into_iter()
+        .map(|result| {
+            let mut result = result.into_tuple().unwrap().into_iter();
+
+            CallResult {
+                success: result.next().unwrap().into_bool().unwrap(),
+                return_data: result.next().unwrap().into_bytes().unwrap().into(),
+            }
+        })
+        .collect())
+}
+
+pub async fn aggregate3<M: Middleware>(
+    client: &M,
+    multicall: &Address,
+    calls: &[Call3],
+) -> Result<Vec<CallResult>, String> {
+    let tx: TypedTransaction = TransactionRequest::new()
+        .to(*multicall)
+        .data(encode_aggregate3(calls))
+        .into();
+
+    let response = client.call(&tx, None).await.map_err(|e| e.to_string())?;
+    let results = decode_aggregate3(&response)?;
+
+    if results.len() != calls.len() {
+        return Err(format!(
+            "Expected {} multicall results, got {}",
+            calls.len(),
+            results.len()
+        ));
+    }
+
+    Ok(results)
+}
+
+// splits calls into several aggregate3 requests of at most `batch_size` calls
+pub async fn aggregate3_batched<M: Middleware>(
+    client: &M,
+    multicall: &Address,
+    calls: &[Call3],
+    batch_size: usize,
+) -> Result<Vec<CallResult>, String> {
+    if batch_size == 0 {
+        return Err("Batch size should be positive".to_string());
+    }
+
+    let mut results = Vec::with_capacity(calls.len());
+
+    for batch in calls.chunks(batch_size) {
+        results.extend(aggregate3(client, multicall, batch).await?);
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::{
+        abi::{encode, Token},
+        types::{Address, Bytes},
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        decode_aggregate3, encode_aggregate3,
+        types::{Call3, CallResult},
+    };
+
+    #[test]
+    fn should_encode_aggregate3() {
+        let calldata = encode_aggregate3(&[Call3 {
+            target: Address::from_low_u64_be(1),
+            allow_failure: true,
+            call_data: Bytes::from(vec![0x12, 0x34]),
+        }]);
+
+        assert_eq!(hex::encode(&calldata[0..4]), "82ad56cb");
+    }
+
+    #[test]
+    fn should_decode_aggregate3_results() {
+        let data = encode(&[Token::Array(vec![
+            Token::Tuple(vec![Token::Bool(true), Token::Bytes(vec![1])]),
+            Token::Tuple(vec![Token::Bool(false), Token::Bytes(vec![])]),
+        ])]);
+
+        assert_eq!(
+            decode_aggregate3(&data),
+            Ok(vec![
+                CallResult {
+                    success: true,
+                    return_data: Bytes::from(vec![1]),
+                },
+                CallResult {
+                    success: false,
+                    return_data: Bytes::default(),
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,14 @@
+use ethers::types::{Address, Bytes};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call3 {
+    pub target: Address,
+    pub allow_failure: bool,
+    pub call_data: Bytes,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallResult {
+    pub success: bool,
+    pub return_data: Bytes,
+}
@@ -0,0 +1,383 @@
+pub mod types;
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    ops::RangeBounds,
+    str::FromStr,
+};
+
+use ethers::types::{Address, H256, U256};
+
+use crate::{
+    auction_calculator::types::AuctionCalculator,
+    constants::{get_network_address, NetworkEnum, SETTLEMENT_CONTRACT_ADDRESS_MAP},
+    limit_order::{
+        eip712::order_typed_data_builder::get_limit_order_v3_domain, types::LimitOrderV3Struct,
+        LimitOrder,
+    },
+    order_state::types::{OrderState, OrderStateEvaluator},
+};
+
+use types::{BookOrder, FillableOrder, OrderBook};
+
+impl OrderBook {
+    pub fn new(network: NetworkEnum) -> Self {
+        OrderBook {
+            domain: get_limit_order_v3_domain(&U256::from(network as u64)),
+            settlement: get_network_address(&SETTLEMENT_CONTRACT_ADDRESS_MAP, network),
+            orders: HashMap::new(),
+            by_maker: HashMap::new(),
+            by_pair: HashMap::new(),
+            by_auction_start: Default::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    pub fn get(&self, order_hash: &H256) -> Option<&BookOrder> {
+        self.orders.get(order_hash)
+    }
+
+    // replaces the order with the same hash
+    pub fn insert(&mut self, order: LimitOrderV3Struct) -> Result<H256, String> {
+        if order.making_amount.is_zero() {
+            return Err("Order making amount is zero".to_string());
+        }
+
+        let state = OrderStateEvaluator::from_limit_order_v3_struct(&order, &self.settlement)?;
+        let order_hash = H256::from_str(&LimitOrder::get_order_hash_static(
+            &order,
+            Some(&self.domain),
+        ))
+        .unwrap();
+
+        self.remove(&order_hash);
+
+        self.by_maker
+            .entry(order.maker)
+            .or_default()
+            .insert(order_hash);
+        self.by_pair
+            .entry((order.maker_asset, order.taker_asset))
+            .or_default()
+            .insert(order_hash);
+        self.by_auction_start
+            .entry(state.salt.auction_start_time)
+            .or_default()
+            .insert(order_hash);
+
+        self.orders.insert(
+            order_hash,
+            BookOrder {
+                order_hash,
+                remaining_making_amount: order.making_amount,
+                calculator: AuctionCalculator::from_auction_data(&state.suffix, &state.salt),
+                state,
+                order,
+            },
+        );
+
+        Ok(order_hash)
+    }
+
+    pub fn remove(&mut self, order_hash: &H256) -> Option<BookOrder> {
+        let book_order = self.orders.remove(order_hash)?;
+        let order = &book_order.order;
+
+        remove_from_index(&mut self.by_maker, &order.maker, order_hash);
+        remove_from_index(
+            &mut self.by_pair,
+            &(order.maker_asset, order.taker_asset),
+            order_hash,
+        );
+
+        let start = book_order.state.salt.auction_start_time;
+        if let Some(hashes) = self.by_auction_start.get_mut(&start) {
+            hashes.remove(order_hash);
+
+            if hashes.is_empty() {
+                self.by_auction_start.remove(&start);
+            }
+        }
+
+        Some(book_order)
+    }
+
+    // OrderFilled(maker, orderHash, remaining) event, fully filled orders are removed
+    pub fn on_filled(&mut self, order_hash: &H256, remaining: U256) -> Option<&BookOrder> {
+        if remaining.is_zero() {
+            self.remove(order_hash);
+
+            return None;
+        }
+
+        let book_order = self.orders.get_mut(order_hash)?;
+        book_order.remaining_making_amount = remaining;
+
+        Some(book_order)
+    }
+
+    // OrderCanceled(maker, orderHash, remainingRaw) event
+    pub fn on_canceled(&mut self, order_hash: &H256) -> Option<BookOrder> {
+        self.remove(order_hash)
+    }
+
+    pub fn by_maker(&self, maker: &Address) -> Vec<&BookOrder> {
+        self.collect(self.by_maker.get(maker))
+    }
+
+    pub fn by_pair(&self, maker_asset: &Address, taker_asset: &Address) -> Vec<&BookOrder> {
+        self.collect(self.by_pair.get(&(*maker_asset, *taker_asset)))
+    }
+
+    // orders sorted by auction start time
+    pub fn by_auction_start(&self, range: impl RangeBounds<u64>) -> Vec<&BookOrder> {
+        self.by_auction_start
+            .range(range)
+            .flat_map(|(_, hashes)| hashes.iter())
+            .map(|hash| &self.orders[hash])
+            .collect()
+    }
+
+    // orders resolver is able to fill at `time`, the lowest rate bump goes first,
+    // orders which amounts overflow uint256 are skipped
+    pub fn fillable_by(&self, resolver: &Address, time: u64) -> Vec<FillableOrder<'_>> {
+        // auction timestamps are uint32, every auction is over after that
+        let auction_time = u32::try_from(time).unwrap_or(u32::MAX);
+
+        let mut fillable: Vec<FillableOrder> = self
+            .orders
+            .values()
+            .filter(|book_order| match book_order.state.state(time) {
+                OrderState::Public { .. } => true,
+                OrderState::Exclusive { resolvers, .. } => resolvers.contains(resolver),
+                _ => false,
+            })
+            .filter_map(|book_order| {
+                let rate_bump = book_order.calculator.calc_rate_bump(auction_time);
+                let order = &book_order.order;
+                let taking_amount = order
+                    .taking_amount
+                    .checked_mul(book_order.remaining_making_amount)?
+                    .checked_div(order.making_amount)?;
+
+                Some(FillableOrder {
+                    order: book_order,
+                    rate_bump,
+                    taking_amount: book_order
+                        .calculator
+                        .checked_auction_taking_amount(&taking_amount, rate_bump)?,
+                })
+            })
+            .collect();
+
+        fillable.sort_by_key(|fillable| (fillable.rate_bump, fillable.order.order_hash));
+
+        fillable
+    }
+
+    fn collect(&self, hashes: Option<&BTreeSet<H256>>) -> Vec<&BookOrder> {
+        hashes
+            .into_iter()
+            .flatten()
+            .map(|hash| &self.orders[hash])
+            .collect()
+    }
+}
+
+fn remove_from_index<K: Eq + std::hash::Hash>(
+    index: &mut HashMap<K, BTreeSet<H256>>,
+    key: &K,
+    order_hash: &H256,
+) {
+    if let Some(hashes) = index.get_mut(key) {
+        hashes.remove(order_hash);
+
+        if hashes.is_empty() {
+            index.remove(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::{Address, U256};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        auction_suffix::parser::types::AuctionPoint,
+        constants::NetworkEnum,
+        limit_order::types::LimitOrderV3Struct,
+        test_utils::{usdc, weth, FusionOrderBuilder},
+    };
+
+    use super::types::OrderBook;
+
+    fn address(n: u64) -> Address {
+        Address::from_low_u64_be(n)
+    }
+
+    // 100 seconds auction from 10% rate bump down to 0.05% at 50 seconds
+    fn order(
+        maker: u64,
+        maker_asset: Address,
+        start: u64,
+        resolvers: &[Address],
+    ) -> LimitOrderV3Struct {
+        let taker_asset = if maker_asset == weth() {
+            usdc()
+        } else {
+            weth()
+        };
+
+        FusionOrderBuilder::new()
+            .with_maker(address(maker))
+            .with_assets(maker_asset, taker_asset)
+            .with_amounts(U256::from(1000), U256::from(2000))
+            .with_salt(maker)
+            .with_auction(start, 100, 1_000_000)
+            .with_points(vec![AuctionPoint {
+                coefficient: 5000,
+                delay: 50,
+            }])
+            .with_whitelist(start as u32, 0, resolvers)
+            .with_public_resolving_deadline(start as u32 + 50)
+            .build()
+    }
+
+    fn resolver(n: u64) -> Address {
+        address(0x1000 + n)
+    }
+
+    #[test]
+    fn should_index_orders() {
+        let mut book = OrderBook::new(NetworkEnum::ETHEREUM);
+
+        let first = book.insert(order(1, weth(), 1000, &[resolver(1)])).unwrap();
+        let second = book.insert(order(1, usdc(), 2000, &[resolver(1)])).unwrap();
+        let third = book.insert(order(2, weth(), 3000, &[resolver(1)])).unwrap();
+
+        let hashes = |orders: Vec<&super::types::BookOrder>| {
+            orders.iter().map(|o| o.order_hash).collect::<Vec<_>>()
+        };
+
+        assert_eq!(book.len(), 3);
+        assert_eq!(hashes(book.by_maker(&address(1))).len(), 2);
+        assert_eq!(hashes(book.by_pair(&usdc(), &weth())), vec![second]);
+        assert_eq!(
+            hashes(book.by_auction_start(1000..=2000)),
+            vec![first, second]
+        );
+        assert_eq!(hashes(book.by_auction_start(2500..)), vec![third]);
+    }
+
+    #[test]
+    fn should_refuse_order_of_other_network() {
+        let mut book = OrderBook::new(NetworkEnum::POLYGON);
+
+        assert!(book
+            .insert(order(1, weth(), 1000, &[resolver(1)]))
+            .unwrap_err()
+            .contains("is not settlement contract"));
+        assert!(book.is_empty());
+    }
+
+    #[test]
+    fn should_update_orders_on_events() {
+        let mut book = OrderBook::new(NetworkEnum::ETHEREUM);
+
+        let first = book.insert(order(1, weth(), 1000, &[resolver(1)])).unwrap();
+        let second = book.insert(order(2, weth(), 1000, &[resolver(1)])).unwrap();
+
+        assert_eq!(
+            book.on_filled(&first, U256::from(400))
+                .map(|o| o.remaining_making_amount),
+            Some(U256::from(400))
+        );
+        assert_eq!(book.on_filled(&first, U256::zero()), None);
+        assert!(book.on_canceled(&second).is_some());
+
+        assert!(book.is_empty());
+        assert!(book.by_maker(&address(1)).is_empty());
+        assert!(book.by_pair(&weth(), &usdc()).is_empty());
+        assert!(book.by_auction_start(..).is_empty());
+    }
+
+    #[test]
+    fn should_list_fillable_orders_sorted_by_rate() {
+        let mut book = OrderBook::new(NetworkEnum::ETHEREUM);
+
+        let early = book.insert(order(1, weth(), 1000, &[resolver(1)])).unwrap();
+        let late = book.insert(order(2, weth(), 1020, &[resolver(1)])).unwrap();
+        let exclusive = book.insert(order(3, weth(), 1000, &[resolver(2)])).unwrap();
+        book.on_filled(&early, U256::from(500));
+
+        let fillable = book.fillable_by(&resolver(1), 1040);
+
+        assert_eq!(
+            fillable
+                .iter()
+                .map(|f| (f.order.order_hash, f.rate_bump, f.taking_amount))
+                .collect::<Vec<_>>(),
+            vec![
+                (early, 204000, U256::from(1020)),
+                (late, 602000, U256::from(2120)),
+            ]
+        );
+        assert_eq!(
+            book.fillable_by(&resolver(2), 1040)
+                .iter()
+                .map(|f| f.order.order_hash)
+                .collect::<Vec<_>>(),
+            vec![exclusive]
+        );
+        // every order is public after its deadline
+        assert_eq!(book.fillable_by(&resolver(3), 1071).len(), 3);
+        // auction is over after uint32 timestamps
+        assert_eq!(
+            book.fillable_by(&resolver(3), u64::from(u32::MAX) + 1)
+                .iter()
+                .map(|f| f.rate_bump)
+                .collect::<Vec<_>>(),
+            vec![0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn should_refuse_order_without_making_amount() {
+        let mut book = OrderBook::new(NetworkEnum::ETHEREUM);
+        let mut order = order(1, weth(), 1000, &[resolver(1)]);
+        order.making_amount = U256::zero();
+
+        assert_eq!(
+            book.insert(order),
+            Err("Order making amount is zero".to_string())
+        );
+        assert!(book.is_empty());
+    }
+
+    #[test]
+    fn should_skip_orders_with_overflowing_amounts() {
+        let mut book = OrderBook::new(NetworkEnum::ETHEREUM);
+
+        let mut overflowing = order(1, weth(), 1000, &[resolver(1)]);
+        overflowing.making_amount = U256::MAX;
+        overflowing.taking_amount = U256::MAX;
+        book.insert(overflowing).unwrap();
+        let fitting = book.insert(order(2, weth(), 1000, &[resolver(1)])).unwrap();
+
+        assert_eq!(
+            book.fillable_by(&resolver(1), 1040)
+                .iter()
+                .map(|f| f.order.order_hash)
+                .collect::<Vec<_>>(),
+            vec![fitting]
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use ethers::types::{transaction::eip712::EIP712Domain, Address, H256, U256};
+
+use crate::{
+    auction_calculator::types::AuctionCalculator, limit_order::types::LimitOrderV3Struct,
+    order_state::types::OrderStateEvaluator,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookOrder {
+    pub order_hash: H256,
+    pub order: LimitOrderV3Struct,
+    pub state: OrderStateEvaluator,
+    pub calculator: AuctionCalculator,
+    pub remaining_making_amount: U256,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillableOrder<'a> {
+    pub order: &'a BookOrder,
+    pub rate_bump: u32,
+    // taking amount for the remaining making amount at the current rate
+    pub taking_amount: U256,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderBook {
+    pub(super) domain: EIP712Domain,
+    pub(super) settlement: Address,
+    pub(super) orders: HashMap<H256, BookOrder>,
+    pub(super) by_maker: HashMap<Address, BTreeSet<H256>>,
+    pub(super) by_pair: HashMap<(Address, Address), BTreeSet<H256>>,
+    pub(super) by_auction_start: BTreeMap<u64, BTreeSet<H256>>,
+}
@@ -0,0 +1,13 @@
+// weights of the checks, a fusion order passing all of them scores 100
+pub const AUCTION_SUFFIX_WEIGHT: u8 = 50;
+pub const SETTLEMENT_ALLOWED_SENDER_WEIGHT: u8 = 30;
+pub const AUCTION_SALT_WEIGHT: u8 = 20;
+
+// auction suffix and at least one more check
+pub const FUSION_THRESHOLD: u8 = 60;
+
+// 2022-12-01, fusion orders did not exist before
+pub const MIN_AUCTION_START_TIME: u64 = 1_669_852_800;
+// orders are signed shortly before the auction starts
+pub const MAX_AUCTION_START_DELAY: u64 = 24 * 60 * 60;
+pub const MAX_AUCTION_DURATION: u32 = 24 * 60 * 60;
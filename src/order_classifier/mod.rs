@@ -0,0 +1,333 @@
+pub mod constants;
+pub mod types;
+
+use ethers::types::Address;
+
+use crate::{
+    auction_calculator::constants::RATE_BUMP_DENOMINATOR,
+    auction_salt::types::AuctionSalt,
+    auction_suffix::types::AuctionSuffix,
+    constants::{get_network_address, NetworkEnum, SETTLEMENT_CONTRACT_ADDRESS_MAP},
+    limit_order::types::LimitOrderV3Struct,
+};
+
+use constants::*;
+use types::{Classification, ClassificationReason, OrderClassifier, OrderKind};
+
+impl Default for OrderClassifier {
+    // settlement contracts of all supported networks
+    fn default() -> Self {
+        Self::with_settlements(
+            SETTLEMENT_CONTRACT_ADDRESS_MAP
+                .iter()
+                .map(|(network, _)| {
+                    (
+                        *network,
+                        get_network_address(&SETTLEMENT_CONTRACT_ADDRESS_MAP, *network),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+impl OrderClassifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_settlements(settlements: Vec<(NetworkEnum, Address)>) -> Self {
+        OrderClassifier { settlements }
+    }
+
+    // time is the timestamp the order was seen at, e.g. its block timestamp
+    pub fn classify(&self, order: &LimitOrderV3Struct, time: u64) -> Classification {
+        let mut score = 0;
+        let mut reasons = vec![];
+
+        let allowed_sender_network = self
+            .settlements
+            .iter()
+            .find(|(_, settlement)| *settlement == order.allowed_sender);
+
+        match allowed_sender_network {
+            Some(_) => {
+                score += SETTLEMENT_ALLOWED_SENDER_WEIGHT;
+                reasons.push(ClassificationReason::SettlementAllowedSender);
+            }
+            None => reasons.push(ClassificationReason::ForeignAllowedSender(
+                order.allowed_sender,
+            )),
+        }
+
+        // suffix has to be addressed to the allowed sender settlement, to any of them otherwise
+        let candidates =
+            allowed_sender_network.map_or(self.settlements.as_slice(), std::slice::from_ref);
+
+        let network = match find_auction_suffix(order, candidates) {
+            Ok(network) => {
+                score += AUCTION_SUFFIX_WEIGHT;
+                reasons.push(ClassificationReason::AuctionSuffix);
+
+                Some(network)
+            }
+            Err(error) => {
+                reasons.push(ClassificationReason::NoAuctionSuffix(error));
+
+                allowed_sender_network.map(|(network, _)| *network)
+            }
+        };
+
+        match validate_auction_salt(&AuctionSalt::decode(&order.salt), time) {
+            Ok(()) => {
+                score += AUCTION_SALT_WEIGHT;
+                reasons.push(ClassificationReason::PlausibleAuctionSalt);
+            }
+            Err(error) => reasons.push(ClassificationReason::ImplausibleAuctionSalt(error)),
+        }
+
+        if score >= FUSION_THRESHOLD {
+            Classification {
+                kind: OrderKind::Fusion,
+                confidence: score,
+                network,
+                reasons,
+            }
+        } else {
+            Classification {
+                kind: OrderKind::Plain,
+                confidence: 100 - score,
+                network,
+                reasons,
+            }
+        }
+    }
+
+    pub fn is_fusion(&self, order: &LimitOrderV3Struct, time: u64) -> bool {
+        self.classify(order, time).kind == OrderKind::Fusion
+    }
+}
+
+// network of the first settlement the post interaction is addressed to,
+// the error of the first candidate if there is none
+fn find_auction_suffix(
+    order: &LimitOrderV3Struct,
+    candidates: &[(NetworkEnum, Address)],
+) -> Result<NetworkEnum, String> {
+    let mut first_error = None;
+
+    for (network, settlement) in candidates.iter() {
+        match AuctionSuffix::from_limit_order_v3_struct(order, settlement) {
+            Ok(_) => return Ok(*network),
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+
+    Err(first_error.unwrap_or_else(|| "No settlement contracts to check".to_string()))
+}
+
+// salt of a plain order is random, so its auction fields rarely look sane
+fn validate_auction_salt(salt: &AuctionSalt, time: u64) -> Result<(), String> {
+    if salt.auction_start_time < MIN_AUCTION_START_TIME
+        || salt.auction_start_time > time.saturating_add(MAX_AUCTION_START_DELAY)
+    {
+        return Err(format!(
+            "Auction start time {} is out of range",
+            salt.auction_start_time
+        ));
+    }
+
+    if salt.duration == 0 || salt.duration > MAX_AUCTION_DURATION {
+        return Err(format!(
+            "Auction duration {} is out of range",
+            salt.duration
+        ));
+    }
+
+    if salt.initial_rate_bump > RATE_BUMP_DENOMINATOR {
+        return Err(format!(
+            "Initial rate bump {} is greater than 100%",
+            salt.initial_rate_bump
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::types::{Address, U256};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        constants::{get_network_address, NetworkEnum, SETTLEMENT_CONTRACT_ADDRESS_MAP},
+        limit_order::{types::LimitOrderV3Struct, LimitOrder, OrderInfoData},
+        test_utils::{settlement, usdc, weth, FusionOrderBuilder},
+    };
+
+    use super::types::{Classification, ClassificationReason, OrderClassifier, OrderKind};
+
+    // shortly before the auction of FusionOrderBuilder starts
+    const TIME: u64 = 1673548100;
+
+    fn plain_order() -> LimitOrderV3Struct {
+        LimitOrder::new(
+            &OrderInfoData {
+                maker_asset: weth(),
+                taker_asset: usdc(),
+                making_amount: U256::from(1000),
+                taking_amount: U256::from(2000),
+                maker: Address::from_low_u64_be(1),
+                salt: Some(U256::from_str("0x1234567890abcdef").unwrap()),
+                allowed_sender: None,
+                receiver: None,
+            },
+            None,
+            &|| U256::zero(),
+        )
+        .build()
+    }
+
+    #[test]
+    fn should_classify_fusion_order() {
+        let classifier = OrderClassifier::new();
+        let order = FusionOrderBuilder::new().build();
+
+        assert_eq!(
+            classifier.classify(&order, TIME),
+            Classification {
+                kind: OrderKind::Fusion,
+                confidence: 100,
+                network: Some(NetworkEnum::ETHEREUM),
+                reasons: vec![
+                    ClassificationReason::SettlementAllowedSender,
+                    ClassificationReason::AuctionSuffix,
+                    ClassificationReason::PlausibleAuctionSalt,
+                ],
+            }
+        );
+        assert!(classifier.is_fusion(&order, TIME));
+        assert!(classifier.is_fusion(&order, u64::MAX));
+    }
+
+    #[test]
+    fn should_classify_fusion_order_of_any_network() {
+        let classifier = OrderClassifier::new();
+
+        for (network, _) in SETTLEMENT_CONTRACT_ADDRESS_MAP.iter() {
+            let settlement = get_network_address(&SETTLEMENT_CONTRACT_ADDRESS_MAP, *network);
+            let order = FusionOrderBuilder::new()
+                .with_allowed_sender(settlement)
+                .with_settlement(settlement)
+                .build();
+
+            let classification = classifier.classify(&order, TIME);
+
+            assert_eq!(
+                (classification.kind, classification.confidence),
+                (OrderKind::Fusion, 100)
+            );
+            assert_eq!(classification.network, Some(*network));
+        }
+
+        // network is told by the post interaction target when anyone can fill
+        let polygon = get_network_address(&SETTLEMENT_CONTRACT_ADDRESS_MAP, NetworkEnum::POLYGON);
+        let public = classifier.classify(
+            &FusionOrderBuilder::new()
+                .with_allowed_sender(Address::zero())
+                .with_settlement(polygon)
+                .build(),
+            TIME,
+        );
+
+        assert_eq!((public.kind, public.confidence), (OrderKind::Fusion, 70));
+        assert_eq!(public.network, Some(NetworkEnum::POLYGON));
+    }
+
+    #[test]
+    fn should_classify_plain_order() {
+        let classifier = OrderClassifier::new();
+
+        assert_eq!(
+            classifier.classify(&plain_order(), TIME),
+            Classification {
+                kind: OrderKind::Plain,
+                confidence: 100,
+                network: None,
+                reasons: vec![
+                    ClassificationReason::ForeignAllowedSender(Address::zero()),
+                    ClassificationReason::NoAuctionSuffix(
+                        "Post interaction is too short".to_string()
+                    ),
+                    ClassificationReason::ImplausibleAuctionSalt(
+                        "Auction start time 0 is out of range".to_string()
+                    ),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn should_score_partial_matches() {
+        let classifier = OrderClassifier::new();
+        let foreign = Address::from_low_u64_be(0xbad);
+
+        // suffix and salt are enough even for a different taker restriction
+        let fusion = classifier.classify(
+            &FusionOrderBuilder::new()
+                .with_allowed_sender(foreign)
+                .build(),
+            TIME,
+        );
+
+        assert_eq!((fusion.kind, fusion.confidence), (OrderKind::Fusion, 70));
+
+        // without a parseable suffix settlement would not be able to fill the order
+        let plain = classifier.classify(
+            &FusionOrderBuilder::new().with_settlement(foreign).build(),
+            TIME,
+        );
+
+        assert_eq!((plain.kind, plain.confidence), (OrderKind::Plain, 50));
+        assert_eq!(plain.network, Some(NetworkEnum::ETHEREUM));
+        assert_eq!(
+            plain.reasons[1],
+            ClassificationReason::NoAuctionSuffix(format!(
+                "Post interaction target {:?} is not settlement contract {:?}",
+                foreign,
+                settlement()
+            ))
+        );
+
+        // suffix addressed to settlement of another network than the allowed sender
+        let polygon = get_network_address(&SETTLEMENT_CONTRACT_ADDRESS_MAP, NetworkEnum::POLYGON);
+        let mixed = classifier.classify(
+            &FusionOrderBuilder::new().with_settlement(polygon).build(),
+            TIME,
+        );
+
+        assert_eq!((mixed.kind, mixed.confidence), (OrderKind::Plain, 50));
+
+        let long_auction = classifier.classify(
+            &FusionOrderBuilder::new()
+                .with_auction(1673548149, 1_000_000, 50000)
+                .build(),
+            TIME,
+        );
+
+        assert_eq!(
+            (long_auction.kind, long_auction.confidence),
+            (OrderKind::Fusion, 80)
+        );
+        assert_eq!(
+            long_auction.reasons[2],
+            ClassificationReason::ImplausibleAuctionSalt(
+                "Auction duration 1000000 is out of range".to_string()
+            )
+        );
+    }
+}
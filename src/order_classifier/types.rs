@@ -0,0 +1,34 @@
+use ethers::types::Address;
+
+use crate::constants::NetworkEnum;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderKind {
+    Fusion,
+    Plain,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassificationReason {
+    SettlementAllowedSender,
+    ForeignAllowedSender(Address),
+    // post interaction targets settlement and ends with a valid auction suffix
+    AuctionSuffix,
+    NoAuctionSuffix(String),
+    PlausibleAuctionSalt,
+    ImplausibleAuctionSalt(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Classification {
+    pub kind: OrderKind,
+    pub confidence: u8, // percent
+    // network of the settlement contract the order is bound to, if any
+    pub network: Option<NetworkEnum>,
+    pub reasons: Vec<ClassificationReason>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderClassifier {
+    pub settlements: Vec<(NetworkEnum, Address)>,
+}
@@ -0,0 +1,181 @@
+pub mod types;
+
+use ethers::types::Address;
+
+use crate::{
+    auction_salt::types::AuctionSalt,
+    auction_suffix::types::AuctionSuffix,
+    limit_order::{
+        parser::{parse_interaction_field, validate_offsets},
+        predicate::Predicate,
+        types::LimitOrderV3Struct,
+    },
+};
+use types::{OrderState, OrderStateEvaluator};
+
+// index of predicate in INTERACTIONS_LAYOUT
+const PREDICATE_FIELD: u8 = 4;
+
+impl OrderStateEvaluator {
+    pub fn from_limit_order_v3_struct(
+        order: &LimitOrderV3Struct,
+        settlement: &Address,
+    ) -> Result<Self, String> {
+        validate_offsets(&order.offsets, &order.interactions)?;
+
+        let predicate =
+            parse_interaction_field(&order.offsets, &order.interactions, PREDICATE_FIELD);
+
+        Ok(OrderStateEvaluator {
+            salt: AuctionSalt::decode(&order.salt),
+            suffix: AuctionSuffix::from_limit_order_v3_struct(order, settlement)?.suffix,
+            expiration: Predicate::decode(&predicate).and_then(|p| p.expiration()),
+        })
+    }
+
+    pub fn auction_end(&self) -> u64 {
+        self.salt.auction_start_time + self.salt.duration as u64
+    }
+
+    pub fn public_at(&self) -> u64 {
+        self.suffix.public_resolving_deadline.as_u64() + 1
+    }
+
+    // auction start or the first resolver allowance, whichever is later
+    pub fn starts_at(&self) -> u64 {
+        let first_access = self
+            .suffix
+            .whitelist
+            .iter()
+            .map(|item| item.allowance as u64)
+            .fold(self.public_at(), u64::min);
+
+        self.salt.auction_start_time.max(first_access)
+    }
+
+    pub fn state(&self, time: u64) -> OrderState {
+        if let Some(expired_at) = self.expiration.filter(|e| time >= *e) {
+            return OrderState::Expired { expired_at };
+        }
+
+        if time < self.starts_at() {
+            return OrderState::NotStarted {
+                starts_at: self.starts_at(),
+            };
+        }
+
+        if self.suffix.is_public(time) {
+            return OrderState::Public {
+                expires_at: self.expiration,
+            };
+        }
+
+        OrderState::Exclusive {
+            resolvers: self.suffix.exclusive_resolvers(time),
+            public_at: self.public_at(),
+            expires_at: self.expiration,
+        }
+    }
+
+    // the closest timestamp after `time` at which the state may change
+    pub fn next_change(&self, time: u64) -> Option<u64> {
+        let allowances = self
+            .suffix
+            .whitelist
+            .iter()
+            .map(|item| item.allowance as u64);
+
+        [self.starts_at(), self.public_at()]
+            .into_iter()
+            .chain(allowances)
+            .chain(self.expiration)
+            .filter(|t| *t > time && self.expiration.is_none_or(|e| *t <= e))
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::Address;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        limit_order::{predicate::Predicate, types::LimitOrderV3Struct},
+        test_utils::{settlement, FusionOrderBuilder},
+    };
+
+    use super::types::{OrderState, OrderStateEvaluator};
+
+    fn resolver(n: u64) -> Address {
+        Address::from_low_u64_be(n)
+    }
+
+    fn order(predicate: Option<Predicate>) -> LimitOrderV3Struct {
+        match predicate {
+            Some(predicate) => FusionOrderBuilder::new().with_predicate(predicate).build(),
+            None => FusionOrderBuilder::new().build(),
+        }
+    }
+
+    #[test]
+    fn should_walk_through_order_states() {
+        let evaluator = OrderStateEvaluator::from_limit_order_v3_struct(
+            &order(Some(Predicate::TimestampBelow(1673548400))),
+            &settlement(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            evaluator.state(1673548100),
+            OrderState::NotStarted {
+                starts_at: 1673548200
+            }
+        );
+        assert_eq!(
+            evaluator.state(1673548230),
+            OrderState::Exclusive {
+                resolvers: vec![resolver(1), resolver(2)],
+                public_at: 1673548301,
+                expires_at: Some(1673548400),
+            }
+        );
+        assert_eq!(
+            evaluator.state(1673548301),
+            OrderState::Public {
+                expires_at: Some(1673548400)
+            }
+        );
+        assert_eq!(
+            evaluator.state(1673548400),
+            OrderState::Expired {
+                expired_at: 1673548400
+            }
+        );
+        assert_eq!(evaluator.auction_end(), 1673548329);
+    }
+
+    #[test]
+    fn should_find_next_state_change() {
+        let evaluator = OrderStateEvaluator::from_limit_order_v3_struct(
+            &order(Some(Predicate::TimestampBelow(1673548250))),
+            &settlement(),
+        )
+        .unwrap();
+
+        assert_eq!(evaluator.next_change(1673548100), Some(1673548200));
+        assert_eq!(evaluator.next_change(1673548200), Some(1673548230));
+        assert_eq!(evaluator.next_change(1673548230), Some(1673548250));
+        assert_eq!(evaluator.next_change(1673548250), None);
+    }
+
+    #[test]
+    fn should_never_expire_without_predicate() {
+        let evaluator =
+            OrderStateEvaluator::from_limit_order_v3_struct(&order(None), &settlement()).unwrap();
+
+        assert_eq!(
+            evaluator.state(u32::MAX as u64),
+            OrderState::Public { expires_at: None }
+        );
+    }
+}
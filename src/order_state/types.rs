@@ -0,0 +1,30 @@
+use ethers::types::Address;
+
+use crate::{auction_salt::types::AuctionSalt, auction_suffix::types::AuctionSuffix};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderState {
+    // nobody is able to fill the order yet
+    NotStarted {
+        starts_at: u64,
+    },
+    // only whitelisted resolvers are able to fill the order
+    Exclusive {
+        resolvers: Vec<Address>,
+        public_at: u64,
+        expires_at: Option<u64>,
+    },
+    Public {
+        expires_at: Option<u64>,
+    },
+    Expired {
+        expired_at: u64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderStateEvaluator {
+    pub salt: AuctionSalt,
+    pub suffix: AuctionSuffix,
+    pub expiration: Option<u64>, // unix timestamp from predicate
+}
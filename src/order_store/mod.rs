@@ -0,0 +1,110 @@
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod types;
+
+use std::str::FromStr;
+
+use ethers::types::{Address, Bytes, H256, U256};
+
+use crate::{
+    constants::{get_network_address, NetworkEnum, SETTLEMENT_CONTRACT_ADDRESS_MAP},
+    limit_order::{
+        eip712::order_typed_data_builder::get_limit_order_v3_domain, predicate::Predicate,
+        types::LimitOrderV3Struct, LimitOrder,
+    },
+    order_state::types::OrderStateEvaluator,
+};
+
+use types::{OrderStatus, StoredOrder};
+
+pub trait OrderStore {
+    // inserts the order or replaces the stored one with the same hash
+    fn save(&mut self, order: &StoredOrder) -> Result<(), String>;
+
+    fn get(&self, order_hash: &H256) -> Result<Option<StoredOrder>, String>;
+
+    // returns false if the order is unknown
+    fn update_status(
+        &mut self,
+        order_hash: &H256,
+        status: OrderStatus,
+        remaining_making_amount: U256,
+    ) -> Result<bool, String>;
+
+    fn by_maker(&self, maker: &Address) -> Result<Vec<StoredOrder>, String>;
+
+    fn by_status(&self, status: OrderStatus) -> Result<Vec<StoredOrder>, String>;
+
+    // the highest nonce maker has ever bound an order to
+    fn max_nonce(&self, maker: &Address) -> Result<Option<U256>, String> {
+        Ok(self
+            .by_maker(maker)?
+            .into_iter()
+            .filter_map(|order| order.nonce)
+            .max())
+    }
+}
+
+impl StoredOrder {
+    pub fn new(order: LimitOrderV3Struct, signature: Bytes, network: NetworkEnum) -> Self {
+        let domain = get_limit_order_v3_domain(&U256::from(network as u64));
+        let order_hash =
+            H256::from_str(&LimitOrder::get_order_hash_static(&order, Some(&domain))).unwrap();
+        let nonce = Predicate::decode(LimitOrder::decode(&order).predicate())
+            .and_then(|predicate| predicate.nonce(&order.maker));
+
+        StoredOrder {
+            order_hash,
+            signature,
+            auction: Self::decode_auction(&order, network),
+            nonce,
+            network,
+            remaining_making_amount: order.making_amount,
+            status: OrderStatus::Active,
+            order,
+        }
+    }
+
+    // None for plain limit orders
+    pub fn decode_auction(
+        order: &LimitOrderV3Struct,
+        network: NetworkEnum,
+    ) -> Option<OrderStateEvaluator> {
+        let settlement = get_network_address(&SETTLEMENT_CONTRACT_ADDRESS_MAP, network);
+
+        OrderStateEvaluator::from_limit_order_v3_struct(order, &settlement).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::{Bytes, U256};
+    use pretty_assertions::assert_eq;
+
+    use crate::{constants::NetworkEnum, test_utils::FusionOrderBuilder};
+
+    use super::types::{OrderStatus, StoredOrder};
+
+    #[test]
+    fn should_decode_auction_and_nonce_of_stored_order() {
+        let order = FusionOrderBuilder::new().with_nonce(7).build();
+
+        let stored = StoredOrder::new(
+            order.clone(),
+            Bytes::from(vec![0x1b; 65]),
+            NetworkEnum::ETHEREUM,
+        );
+
+        assert_eq!(stored.nonce, Some(U256::from(7)));
+        assert_eq!(
+            stored.auction.map(|a| a.salt.auction_start_time),
+            Some(1673548149)
+        );
+        assert_eq!(stored.remaining_making_amount, order.making_amount);
+        assert_eq!(stored.status, OrderStatus::Active);
+        assert_eq!(
+            StoredOrder::decode_auction(&order, NetworkEnum::POLYGON),
+            None
+        );
+    }
+}
@@ -0,0 +1,435 @@
+use std::{fmt::Debug, path::Path, str::FromStr};
+
+use ethers::types::{Address, Bytes, H256, U256};
+use rusqlite::{params, types::ToSql, Connection, Row};
+
+use crate::{constants::NetworkEnum, limit_order::types::LimitOrderV3Struct};
+
+use super::{
+    types::{OrderStatus, StoredOrder},
+    OrderStore,
+};
+
+// applied in order, PRAGMA user_version holds the number of applied migrations
+const MIGRATIONS: [&str; 3] = [
+    "CREATE TABLE orders (
+        order_hash TEXT PRIMARY KEY,
+        salt TEXT NOT NULL,
+        maker_asset TEXT NOT NULL,
+        taker_asset TEXT NOT NULL,
+        maker TEXT NOT NULL,
+        receiver TEXT NOT NULL,
+        allowed_sender TEXT NOT NULL,
+        making_amount TEXT NOT NULL,
+        taking_amount TEXT NOT NULL,
+        offsets TEXT NOT NULL,
+        interactions TEXT NOT NULL,
+        signature TEXT NOT NULL,
+        remaining_making_amount TEXT NOT NULL,
+        status TEXT NOT NULL
+    );
+    CREATE INDEX orders_maker ON orders (maker);
+    CREATE INDEX orders_status ON orders (status);",
+    "ALTER TABLE orders ADD COLUMN nonce TEXT;
+    ALTER TABLE orders ADD COLUMN auction_start_time INTEGER;
+    ALTER TABLE orders ADD COLUMN auction_duration INTEGER;
+    ALTER TABLE orders ADD COLUMN initial_rate_bump INTEGER;
+    ALTER TABLE orders ADD COLUMN public_resolving_deadline INTEGER;
+    ALTER TABLE orders ADD COLUMN expiration INTEGER;
+    CREATE INDEX orders_auction_start_time ON orders (auction_start_time);",
+    // orders saved before were ethereum ones
+    "ALTER TABLE orders ADD COLUMN chain_id INTEGER NOT NULL DEFAULT 1;",
+];
+
+const SELECT_ORDER: &str = "SELECT order_hash, salt, maker_asset, taker_asset, maker, receiver,
+    allowed_sender, making_amount, taking_amount, offsets, interactions, signature,
+    remaining_making_amount, status, nonce, chain_id FROM orders";
+
+pub struct SqliteOrderStore {
+    connection: Connection,
+}
+
+impl SqliteOrderStore {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        Self::from_connection(Connection::open(path).map_err(to_string)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, String> {
+        Self::from_connection(Connection::open_in_memory().map_err(to_string)?)
+    }
+
+    // applies pending migrations
+    pub fn from_connection(connection: Connection) -> Result<Self, String> {
+        let mut store = SqliteOrderStore { connection };
+        store.migrate()?;
+
+        Ok(store)
+    }
+
+    pub fn schema_version(&self) -> Result<usize, String> {
+        self.connection
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(to_string)
+    }
+
+    fn migrate(&mut self) -> Result<(), String> {
+        let version = self.schema_version()?;
+
+        if version > MIGRATIONS.len() {
+            return Err(format!(
+                "Database schema version {} is newer than supported {}",
+                version,
+                MIGRATIONS.len()
+            ));
+        }
+
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            let transaction = self.connection.transaction().map_err(to_string)?;
+
+            transaction
+                .execute_batch(migration)
+                .and_then(|_| {
+                    transaction.execute_batch(&format!("PRAGMA user_version = {}", index + 1))
+                })
+                .map_err(|e| format!("Migration {} failed: {}", index + 1, e))?;
+
+            transaction.commit().map_err(to_string)?;
+        }
+
+        Ok(())
+    }
+
+    fn query(&self, filter: &str, params: &[&dyn ToSql]) -> Result<Vec<StoredOrder>, String> {
+        let mut statement = self
+            .connection
+            .prepare(&format!("{} {}", SELECT_ORDER, filter))
+            .map_err(to_string)?;
+        let mut rows = statement.query(params).map_err(to_string)?;
+        let mut orders = vec![];
+
+        while let Some(row) = rows.next().map_err(to_string)? {
+            orders.push(read_order(row)?);
+        }
+
+        Ok(orders)
+    }
+}
+
+impl OrderStore for SqliteOrderStore {
+    fn save(&mut self, stored: &StoredOrder) -> Result<(), String> {
+        let order = &stored.order;
+        let auction = stored.auction.as_ref();
+
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO orders (order_hash, salt, maker_asset, taker_asset,
+                maker, receiver, allowed_sender, making_amount, taking_amount, offsets,
+                interactions, signature, remaining_making_amount, status, nonce,
+                auction_start_time, auction_duration, initial_rate_bump,
+                public_resolving_deadline, expiration, chain_id)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15,
+                ?16, ?17, ?18, ?19, ?20, ?21)",
+                params![
+                    hex_string(&stored.order_hash),
+                    order.salt.to_string(),
+                    hex_string(&order.maker_asset),
+                    hex_string(&order.taker_asset),
+                    hex_string(&order.maker),
+                    hex_string(&order.receiver),
+                    hex_string(&order.allowed_sender),
+                    order.making_amount.to_string(),
+                    order.taking_amount.to_string(),
+                    order.offsets.to_string(),
+                    order.interactions.to_string(),
+                    stored.signature.to_string(),
+                    stored.remaining_making_amount.to_string(),
+                    stored.status.to_string(),
+                    stored.nonce.map(|nonce| nonce.to_string()),
+                    auction.map(|a| a.salt.auction_start_time as i64),
+                    auction.map(|a| a.salt.duration),
+                    auction.map(|a| a.salt.initial_rate_bump),
+                    auction.map(|a| a.suffix.public_resolving_deadline.as_u64() as i64),
+                    auction.and_then(|a| a.expiration).map(|e| e as i64),
+                    stored.network as i64,
+                ],
+            )
+            .map_err(to_string)?;
+
+        Ok(())
+    }
+
+    fn get(&self, order_hash: &H256) -> Result<Option<StoredOrder>, String> {
+        Ok(self
+            .query("WHERE order_hash = ?1", &[&hex_string(order_hash)])?
+            .pop())
+    }
+
+    fn update_status(
+        &mut self,
+        order_hash: &H256,
+        status: OrderStatus,
+        remaining_making_amount: U256,
+    ) -> Result<bool, String> {
+        let updated = self
+            .connection
+            .execute(
+                "UPDATE orders SET status = ?1, remaining_making_amount = ?2
+                WHERE order_hash = ?3",
+                params![
+                    status.to_string(),
+                    remaining_making_amount.to_string(),
+                    hex_string(order_hash)
+                ],
+            )
+            .map_err(to_string)?;
+
+        Ok(updated > 0)
+    }
+
+    fn by_maker(&self, maker: &Address) -> Result<Vec<StoredOrder>, String> {
+        self.query(
+            "WHERE maker = ?1 ORDER BY auction_start_time, order_hash",
+            &[&hex_string(maker)],
+        )
+    }
+
+    fn by_status(&self, status: OrderStatus) -> Result<Vec<StoredOrder>, String> {
+        self.query(
+            "WHERE status = ?1 ORDER BY auction_start_time, order_hash",
+            &[&status.to_string()],
+        )
+    }
+}
+
+fn read_order(row: &Row) -> Result<StoredOrder, String> {
+    let column = |index: usize| row.get::<_, String>(index).map_err(to_string);
+
+    let order = LimitOrderV3Struct {
+        salt: parse_dec(&column(1)?)?,
+        maker_asset: parse(&column(2)?)?,
+        taker_asset: parse(&column(3)?)?,
+        maker: parse(&column(4)?)?,
+        receiver: parse(&column(5)?)?,
+        allowed_sender: parse(&column(6)?)?,
+        making_amount: parse_dec(&column(7)?)?,
+        taking_amount: parse_dec(&column(8)?)?,
+        offsets: parse_dec(&column(9)?)?,
+        interactions: parse(&column(10)?)?,
+    };
+    let nonce = row
+        .get::<_, Option<String>>(14)
+        .map_err(to_string)?
+        .map(|nonce| parse_dec(&nonce))
+        .transpose()?;
+    let chain_id = row.get::<_, i64>(15).map_err(to_string)?;
+    let network = NetworkEnum::from_chain_id(chain_id as u64)
+        .ok_or(format!("Unsupported stored chain id {}", chain_id))?;
+
+    Ok(StoredOrder {
+        order_hash: parse(&column(0)?)?,
+        signature: parse::<Bytes>(&column(11)?)?,
+        auction: StoredOrder::decode_auction(&order, network),
+        nonce,
+        network,
+        remaining_making_amount: parse_dec(&column(12)?)?,
+        status: column(13)?.parse()?,
+        order,
+    })
+}
+
+fn hex_string<T: Debug>(value: &T) -> String {
+    format!("{:?}", value)
+}
+
+fn parse<T: FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid stored value {}", value))
+}
+
+fn parse_dec(value: &str) -> Result<U256, String> {
+    U256::from_dec_str(value).map_err(|_| format!("Invalid stored amount {}", value))
+}
+
+fn to_string<E: ToString>(error: E) -> String {
+    error.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::{Address, Bytes, U256};
+    use pretty_assertions::assert_eq;
+    use rusqlite::Connection;
+
+    use crate::{
+        constants::NetworkEnum,
+        limit_order::{LimitOrder, OrderInfoData},
+        order_store::{
+            types::{OrderStatus, StoredOrder},
+            OrderStore,
+        },
+        test_utils::FusionOrderBuilder,
+    };
+
+    use super::{SqliteOrderStore, MIGRATIONS};
+
+    fn maker(n: u64) -> Address {
+        Address::from_low_u64_be(n)
+    }
+
+    fn limit_order(maker: Address, salt: u64) -> LimitOrder {
+        LimitOrder::new(
+            &OrderInfoData {
+                maker_asset: Address::from_low_u64_be(0xaa),
+                taker_asset: Address::from_low_u64_be(0xbb),
+                making_amount: U256::from(1000),
+                taking_amount: U256::from(2000),
+                maker,
+                salt: Some(U256::from(salt)),
+                allowed_sender: None,
+                receiver: None,
+            },
+            None,
+            &|| U256::zero(),
+        )
+    }
+
+    fn fusion_order(maker: Address, start: u64, nonce: u64) -> LimitOrder {
+        FusionOrderBuilder::new()
+            .with_maker(maker)
+            .with_salt(nonce)
+            .with_auction(start, 180, 50000)
+            .with_nonce(nonce)
+            .build_limit_order()
+    }
+
+    fn stored(order: LimitOrder) -> StoredOrder {
+        StoredOrder::new(
+            order.build(),
+            Bytes::from(vec![0x1b; 65]),
+            NetworkEnum::ETHEREUM,
+        )
+    }
+
+    #[test]
+    fn should_apply_migrations() {
+        let store = SqliteOrderStore::open_in_memory().unwrap();
+
+        assert_eq!(store.schema_version().unwrap(), MIGRATIONS.len());
+
+        // reapplying is no-op
+        let store = SqliteOrderStore::from_connection(store.connection).unwrap();
+
+        assert_eq!(store.schema_version().unwrap(), MIGRATIONS.len());
+    }
+
+    #[test]
+    fn should_upgrade_existing_database() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(&format!("{} PRAGMA user_version = 1;", MIGRATIONS[0]))
+            .unwrap();
+        connection
+            .execute(
+                "INSERT INTO orders VALUES ('0x01', '1', '0x02', '0x03', '0x04', '0x05',
+                '0x06', '1', '2', '0', '0x', '0x', '1', 'active')",
+                [],
+            )
+            .unwrap();
+
+        let store = SqliteOrderStore::from_connection(connection).unwrap();
+
+        assert_eq!(store.schema_version().unwrap(), MIGRATIONS.len());
+        assert_eq!(
+            store
+                .connection
+                .query_row(
+                    "SELECT COUNT(*) FROM orders WHERE nonce IS NULL AND chain_id = 1",
+                    [],
+                    |row| row.get::<_, i64>(0)
+                )
+                .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn should_refuse_newer_schema() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection.execute_batch("PRAGMA user_version = 4").unwrap();
+
+        assert_eq!(
+            SqliteOrderStore::from_connection(connection).err(),
+            Some("Database schema version 4 is newer than supported 3".to_string())
+        );
+    }
+
+    #[test]
+    fn should_save_and_load_orders() {
+        let mut store = SqliteOrderStore::open_in_memory().unwrap();
+        let fusion = stored(fusion_order(maker(1), 1000, 7));
+        let limit = stored(limit_order(maker(1), 42));
+
+        store.save(&fusion).unwrap();
+        store.save(&limit).unwrap();
+
+        assert_eq!(store.get(&fusion.order_hash).unwrap(), Some(fusion.clone()));
+        assert_eq!(store.get(&limit.order_hash).unwrap(), Some(limit.clone()));
+        assert!(fusion.auction.is_some());
+        assert_eq!(fusion.nonce, Some(U256::from(7)));
+        assert_eq!(limit.auction, None);
+        assert_eq!(store.get(&Default::default()).unwrap(), None);
+    }
+
+    #[test]
+    fn should_update_status() {
+        let mut store = SqliteOrderStore::open_in_memory().unwrap();
+        let first = stored(fusion_order(maker(1), 1000, 1));
+        let second = stored(fusion_order(maker(1), 2000, 2));
+
+        store.save(&first).unwrap();
+        store.save(&second).unwrap();
+
+        assert!(store
+            .update_status(&first.order_hash, OrderStatus::Filled, U256::zero())
+            .unwrap());
+        assert!(!store
+            .update_status(&Default::default(), OrderStatus::Filled, U256::zero())
+            .unwrap());
+
+        let filled = store.by_status(OrderStatus::Filled).unwrap();
+
+        assert_eq!(filled.len(), 1);
+        assert_eq!(filled[0].order_hash, first.order_hash);
+        assert_eq!(filled[0].remaining_making_amount, U256::zero());
+        assert_eq!(store.by_status(OrderStatus::Active).unwrap(), vec![second]);
+    }
+
+    #[test]
+    fn should_keep_maker_nonce_history() {
+        let mut store = SqliteOrderStore::open_in_memory().unwrap();
+
+        store
+            .save(&stored(fusion_order(maker(1), 2000, 3)))
+            .unwrap();
+        store
+            .save(&stored(fusion_order(maker(1), 1000, 5)))
+            .unwrap();
+        store.save(&stored(limit_order(maker(1), 1))).unwrap();
+        store
+            .save(&stored(fusion_order(maker(2), 1000, 9)))
+            .unwrap();
+
+        assert_eq!(
+            store
+                .by_maker(&maker(1))
+                .unwrap()
+                .iter()
+                .map(|order| order.nonce)
+                .collect::<Vec<_>>(),
+            vec![None, Some(U256::from(5)), Some(U256::from(3))]
+        );
+        assert_eq!(store.max_nonce(&maker(1)).unwrap(), Some(U256::from(5)));
+        assert_eq!(store.max_nonce(&maker(3)).unwrap(), None);
+    }
+}
@@ -0,0 +1,55 @@
+use std::{fmt, str::FromStr};
+
+use ethers::types::{Bytes, H256, U256};
+
+use crate::{
+    constants::NetworkEnum, limit_order::types::LimitOrderV3Struct,
+    order_state::types::OrderStateEvaluator,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrderStatus {
+    Active,
+    Filled,
+    Canceled,
+    Expired,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredOrder {
+    pub order_hash: H256,
+    pub order: LimitOrderV3Struct,
+    pub signature: Bytes,
+    pub auction: Option<OrderStateEvaluator>, // None for plain limit orders
+    pub nonce: Option<U256>,                  // maker nonce the predicate is bound to
+    pub network: NetworkEnum,
+    pub remaining_making_amount: U256,
+    pub status: OrderStatus,
+}
+
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            OrderStatus::Active => "active",
+            OrderStatus::Filled => "filled",
+            OrderStatus::Canceled => "canceled",
+            OrderStatus::Expired => "expired",
+        };
+
+        write!(f, "{}", status)
+    }
+}
+
+impl FromStr for OrderStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "active" => Ok(OrderStatus::Active),
+            "filled" => Ok(OrderStatus::Filled),
+            "canceled" => Ok(OrderStatus::Canceled),
+            "expired" => Ok(OrderStatus::Expired),
+            _ => Err(format!("Unknown order status {}", s)),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use ethers::types::{
+    transaction::eip712::{EIP712Domain, Eip712DomainType, TypedData},
+    Address, U256,
+};
+use serde_json::json;
+
+use crate::limit_order::eip712::domain::eip712_domain_type;
+
+use crate::constants::PERMIT2;
+
+use super::types::{DaiPermitData, Erc2612PermitData, PermitSingle, PermitTransferFrom};
+
+pub const DAI_TYPE_DATA_NAME: &str = "Dai Stablecoin";
+pub const DAI_TYPE_DATA_VERSION: &str = "1";
+pub const PERMIT2_TYPE_DATA_NAME: &str = "Permit2";
+
+pub fn permit_type() -> Vec<Eip712DomainType> {
+    let permit_type_json = json!([
+        {"name": "owner", "type": "address"},
+        {"name": "spender", "type": "address"},
+        {"name": "value", "type": "uint256"},
+        {"name": "nonce", "type": "uint256"},
+        {"name": "deadline", "type": "uint256"}
+    ]);
+
+    serde_json::from_value(permit_type_json).unwrap()
+}
+
+pub fn dai_permit_type() -> Vec<Eip712DomainType> {
+    let permit_type_json = json!([
+        {"name": "holder", "type": "address"},
+        {"name": "spender", "type": "address"},
+        {"name": "nonce", "type": "uint256"},
+        {"name": "expiry", "type": "uint256"},
+        {"name": "allowed", "type": "bool"}
+    ]);
+
+    serde_json::from_value(permit_type_json).unwrap()
+}
+
+// Permit2 domain has no version
+pub fn permit2_domain_type() -> Vec<Eip712DomainType> {
+    let domain_type_json = json!([
+        {"name": "name", "type": "string"},
+        {"name": "chainId", "type": "uint256"},
+        {"name": "verifyingContract", "type": "address"}
+    ]);
+
+    serde_json::from_value(domain_type_json).unwrap()
+}
+
+pub fn permit_details_type() -> Vec<Eip712DomainType> {
+    let permit_type_json = json!([
+        {"name": "token", "type": "address"},
+        {"name": "amount", "type": "uint160"},
+        {"name": "expiration", "type": "uint48"},
+        {"name": "nonce", "type": "uint48"}
+    ]);
+
+    serde_json::from_value(permit_type_json).unwrap()
+}
+
+pub fn permit_single_type() -> Vec<Eip712DomainType> {
+    let permit_type_json = json!([
+        {"name": "details", "type": "PermitDetails"},
+        {"name": "spender", "type": "address"},
+        {"name": "sigDeadline", "type": "uint256"}
+    ]);
+
+    serde_json::from_value(permit_type_json).unwrap()
+}
+
+pub fn token_permissions_type() -> Vec<Eip712DomainType> {
+    let permit_type_json = json!([
+        {"name": "token", "type": "address"},
+        {"name": "amount", "type": "uint256"}
+    ]);
+
+    serde_json::from_value(permit_type_json).unwrap()
+}
+
+pub fn permit_transfer_from_type() -> Vec<Eip712DomainType> {
+    let permit_type_json = json!([
+        {"name": "permitted", "type": "TokenPermissions"},
+        {"name": "spender", "type": "address"},
+        {"name": "nonce", "type": "uint256"},
+        {"name": "deadline", "type": "uint256"}
+    ]);
+
+    serde_json::from_value(permit_type_json).unwrap()
+}
+
+pub fn get_token_domain(
+    name: &str,
+    version: &str,
+    chain_id: &U256,
+    token: &Address,
+) -> EIP712Domain {
+    EIP712Domain {
+        chain_id: Some(*chain_id),
+        verifying_contract: Some(*token),
+        name: Some(name.to_string()),
+        version: Some(version.to_string()),
+        salt: None,
+    }
+}
+
+pub fn get_dai_domain(chain_id: &U256, token: &Address) -> EIP712Domain {
+    get_token_domain(DAI_TYPE_DATA_NAME, DAI_TYPE_DATA_VERSION, chain_id, token)
+}
+
+pub fn build_permit_data(domain: &EIP712Domain, permit: &Erc2612PermitData) -> TypedData {
+    let message = BTreeMap::from([
+        ("owner".to_string(), json!(permit.owner)),
+        ("spender".to_string(), json!(permit.spender)),
+        ("value".to_string(), json!(permit.value.to_string())),
+        ("nonce".to_string(), json!(permit.nonce.to_string())),
+        ("deadline".to_string(), json!(permit.deadline.to_string())),
+    ]);
+
+    build_typed_data(domain, permit_type(), message)
+}
+
+pub fn build_dai_permit_data(domain: &EIP712Domain, permit: &DaiPermitData) -> TypedData {
+    let message = BTreeMap::from([
+        ("holder".to_string(), json!(permit.holder)),
+        ("spender".to_string(), json!(permit.spender)),
+        ("nonce".to_string(), json!(permit.nonce.to_string())),
+        ("expiry".to_string(), json!(permit.expiry.to_string())),
+        ("allowed".to_string(), json!(permit.allowed)),
+    ]);
+
+    build_typed_data(domain, dai_permit_type(), message)
+}
+
+pub fn get_permit2_domain(chain_id: &U256) -> EIP712Domain {
+    EIP712Domain {
+        chain_id: Some(*chain_id),
+        verifying_contract: Some(Address::from_str(PERMIT2).unwrap()),
+        name: Some(PERMIT2_TYPE_DATA_NAME.to_string()),
+        version: None,
+        salt: None,
+    }
+}
+
+pub fn build_permit_single_data(chain_id: &U256, permit: &PermitSingle) -> TypedData {
+    let details = &permit.details;
+    let message = BTreeMap::from([
+        (
+            "details".to_string(),
+            json!({
+                "token": details.token,
+                "amount": details.amount.to_string(),
+                "expiration": details.expiration.to_string(),
+                "nonce": details.nonce.to_string(),
+            }),
+        ),
+        ("spender".to_string(), json!(permit.spender)),
+        (
+            "sigDeadline".to_string(),
+            json!(permit.sig_deadline.to_string()),
+        ),
+    ]);
+
+    TypedData {
+        primary_type: "PermitSingle".to_string(),
+        types: BTreeMap::from([
+            ("EIP712Domain".to_string(), permit2_domain_type()),
+            ("PermitSingle".to_string(), permit_single_type()),
+            ("PermitDetails".to_string(), permit_details_type()),
+        ]),
+        domain: get_permit2_domain(chain_id),
+        message,
+    }
+}
+
+pub fn build_permit_transfer_from_data(chain_id: &U256, permit: &PermitTransferFrom) -> TypedData {
+    let message = BTreeMap::from([
+        (
+            "permitted".to_string(),
+            json!({
+                "token": permit.permitted.token,
+                "amount": permit.permitted.amount.to_string(),
+            }),
+        ),
+        ("spender".to_string(), json!(permit.spender)),
+        ("nonce".to_string(), json!(permit.nonce.to_string())),
+        ("deadline".to_string(), json!(permit.deadline.to_string())),
+    ]);
+
+    TypedData {
+        primary_type: "PermitTransferFrom".to_string(),
+        types: BTreeMap::from([
+            ("EIP712Domain".to_string(), permit2_domain_type()),
+            (
+                "PermitTransferFrom".to_string(),
+                permit_transfer_from_type(),
+            ),
+            ("TokenPermissions".to_string(), token_permissions_type()),
+        ]),
+        domain: get_permit2_domain(chain_id),
+        message,
+    }
+}
+
+fn build_typed_data(
+    domain: &EIP712Domain,
+    permit_type: Vec<Eip712DomainType>,
+    message: BTreeMap<String, serde_json::Value>,
+) -> TypedData {
+    TypedData {
+        primary_type: "Permit".to_string(),
+        types: BTreeMap::from([
+            ("EIP712Domain".to_string(), eip712_domain_type()),
+            ("Permit".to_string(), permit_type),
+        ]),
+        domain: domain.clone(),
+        message,
+    }
+}
@@ -0,0 +1,333 @@
+pub mod eip712;
+pub mod permit2;
+pub mod types;
+
+use ethers::{
+    abi::{decode, encode, ParamType, Token},
+    signers::LocalWallet,
+    types::{
+        transaction::eip712::{EIP712Domain, Eip712, TypedData},
+        Address, Bytes, Signature, H256, U256,
+    },
+};
+
+use eip712::{build_dai_permit_data, build_permit_data};
+use types::{DaiPermitData, Erc2612PermitData, PermitCall, PermitSingle, TokenPermit};
+
+// lengths of permit arguments accepted by SafeERC20.safePermit
+const ERC2612_PERMIT_LENGTH: usize = 7 * 32;
+const DAI_PERMIT_LENGTH: usize = 8 * 32;
+const PERMIT2_PERMIT_LENGTH: usize = 11 * 32;
+
+impl Erc2612PermitData {
+    pub fn typed_data(&self, domain: &EIP712Domain) -> TypedData {
+        build_permit_data(domain, self)
+    }
+
+    pub fn sign(&self, domain: &EIP712Domain, wallet: &LocalWallet) -> Result<TokenPermit, String> {
+        let signature = sign_typed_data(&self.typed_data(domain), wallet)?;
+
+        Ok(TokenPermit {
+            token: verifying_contract(domain)?,
+            call: PermitCall::Erc2612 {
+                owner: self.owner,
+                spender: self.spender,
+                value: self.value,
+                deadline: self.deadline,
+                signature,
+            },
+        })
+    }
+}
+
+impl DaiPermitData {
+    pub fn typed_data(&self, domain: &EIP712Domain) -> TypedData {
+        build_dai_permit_data(domain, self)
+    }
+
+    pub fn sign(&self, domain: &EIP712Domain, wallet: &LocalWallet) -> Result<TokenPermit, String> {
+        let signature = sign_typed_data(&self.typed_data(domain), wallet)?;
+
+        Ok(TokenPermit {
+            token: verifying_contract(domain)?,
+            call: PermitCall::Dai {
+                holder: self.holder,
+                spender: self.spender,
+                nonce: self.nonce,
+                expiry: self.expiry,
+                allowed: self.allowed,
+                signature,
+            },
+        })
+    }
+}
+
+impl TokenPermit {
+    pub fn build(&self) -> Bytes {
+        let tokens = match &self.call {
+            PermitCall::Erc2612 {
+                owner,
+                spender,
+                value,
+                deadline,
+                signature,
+            } => [
+                vec![
+                    Token::Address(*owner),
+                    Token::Address(*spender),
+                    Token::Uint(*value),
+                    Token::Uint(*deadline),
+                ],
+                signature_tokens(signature),
+            ]
+            .concat(),
+            PermitCall::Dai {
+                holder,
+                spender,
+                nonce,
+                expiry,
+                allowed,
+                signature,
+            } => [
+                vec![
+                    Token::Address(*holder),
+                    Token::Address(*spender),
+                    Token::Uint(*nonce),
+                    Token::Uint(*expiry),
+                    Token::Bool(*allowed),
+                ],
+                signature_tokens(signature),
+            ]
+            .concat(),
+            PermitCall::Permit2 { permit, signature } => {
+                return [self.token.as_bytes(), &permit.encode_call(signature)]
+                    .concat()
+                    .into()
+            }
+        };
+
+        [self.token.as_bytes(), &encode(&tokens)].concat().into()
+    }
+
+    pub fn decode(permit: &Bytes) -> Result<Self, String> {
+        if permit.len() < 20 {
+            return Err("Permit is too short".to_string());
+        }
+
+        let token = Address::from_slice(&permit[0..20]);
+        let args = &permit[20..];
+
+        let call = match args.len() {
+            ERC2612_PERMIT_LENGTH => {
+                let types = param_types(&[ParamType::Uint(256), ParamType::Uint(256)]);
+                let mut tokens = decode(&types, args).map_err(|e| e.to_string())?.into_iter();
+
+                PermitCall::Erc2612 {
+                    owner: tokens.next().unwrap().into_address().unwrap(),
+                    spender: tokens.next().unwrap().into_address().unwrap(),
+                    value: tokens.next().unwrap().into_uint().unwrap(),
+                    deadline: tokens.next().unwrap().into_uint().unwrap(),
+                    signature: signature_from_tokens(&mut tokens)?,
+                }
+            }
+            DAI_PERMIT_LENGTH => {
+                let types =
+                    param_types(&[ParamType::Uint(256), ParamType::Uint(256), ParamType::Bool]);
+                let mut tokens = decode(&types, args).map_err(|e| e.to_string())?.into_iter();
+
+                PermitCall::Dai {
+                    holder: tokens.next().unwrap().into_address().unwrap(),
+                    spender: tokens.next().unwrap().into_address().unwrap(),
+                    nonce: tokens.next().unwrap().into_uint().unwrap(),
+                    expiry: tokens.next().unwrap().into_uint().unwrap(),
+                    allowed: tokens.next().unwrap().into_bool().unwrap(),
+                    signature: signature_from_tokens(&mut tokens)?,
+                }
+            }
+            PERMIT2_PERMIT_LENGTH => {
+                let (permit, signature) = PermitSingle::decode_call(args)?;
+
+                PermitCall::Permit2 { permit, signature }
+            }
+            length => return Err(format!("Unknown permit length {}", length)),
+        };
+
+        Ok(TokenPermit { token, call })
+    }
+}
+
+fn sign_typed_data(typed_data: &TypedData, wallet: &LocalWallet) -> Result<Signature, String> {
+    let hash = typed_data.encode_eip712().map_err(|e| e.to_string())?;
+
+    Ok(wallet.sign_hash(H256::from(hash)))
+}
+
+fn verifying_contract(domain: &EIP712Domain) -> Result<Address, String> {
+    domain
+        .verifying_contract
+        .ok_or_else(|| "Token domain has no verifying contract".to_string())
+}
+
+fn signature_tokens(signature: &Signature) -> Vec<Token> {
+    vec![
+        Token::Uint(U256::from(signature.v)),
+        Token::FixedBytes(word(&signature.r)),
+        Token::FixedBytes(word(&signature.s)),
+    ]
+}
+
+fn word(value: &U256) -> Vec<u8> {
+    let mut word = vec![0; 32];
+    value.to_big_endian(&mut word);
+
+    word
+}
+
+fn signature_from_tokens(tokens: &mut impl Iterator<Item = Token>) -> Result<Signature, String> {
+    let v = tokens.next().unwrap().into_uint().unwrap();
+    let v = u8::try_from(v).map_err(|_| format!("Signature v {} does not fit into uint8", v))?;
+    let r = tokens.next().unwrap().into_fixed_bytes().unwrap();
+    let s = tokens.next().unwrap().into_fixed_bytes().unwrap();
+
+    Ok(Signature {
+        r: U256::from(r.as_slice()),
+        s: U256::from(s.as_slice()),
+        v: u64::from(v),
+    })
+}
+
+fn param_types(leading: &[ParamType]) -> Vec<ParamType> {
+    [
+        &[ParamType::Address, ParamType::Address],
+        leading,
+        &[
+            ParamType::Uint(8),
+            ParamType::FixedBytes(32),
+            ParamType::FixedBytes(32),
+        ],
+    ]
+    .concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::{
+        abi::{encode, Token},
+        signers::Signer,
+        types::{Address, Bytes, H256, U256},
+        utils::keccak256,
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::test_utils::{router_v5, usdc, wallet};
+
+    use super::{
+        eip712::{get_dai_domain, get_token_domain},
+        types::{DaiPermitData, Erc2612PermitData, PermitCall, TokenPermit},
+    };
+
+    fn erc2612_permit() -> Erc2612PermitData {
+        Erc2612PermitData {
+            owner: wallet().address(),
+            spender: router_v5(),
+            value: U256::from(1420000000),
+            nonce: U256::from(2),
+            deadline: U256::from(1673548200),
+        }
+    }
+
+    #[test]
+    fn should_hash_erc2612_permit_as_eip712() {
+        let domain = get_token_domain("USD Coin", "2", &U256::from(1), &usdc());
+        let permit = erc2612_permit();
+
+        let domain_separator = keccak256(encode(&[
+            Token::FixedBytes(keccak256("EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)").to_vec()),
+            Token::FixedBytes(keccak256("USD Coin").to_vec()),
+            Token::FixedBytes(keccak256("2").to_vec()),
+            Token::Uint(U256::from(1)),
+            Token::Address(usdc()),
+        ]));
+        let struct_hash = keccak256(encode(&[
+            Token::FixedBytes(keccak256("Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)").to_vec()),
+            Token::Address(permit.owner),
+            Token::Address(permit.spender),
+            Token::Uint(permit.value),
+            Token::Uint(permit.nonce),
+            Token::Uint(permit.deadline),
+        ]));
+        let hash = keccak256([&[0x19, 0x01], &domain_separator[..], &struct_hash[..]].concat());
+
+        let signed = permit.sign(&domain, &wallet()).unwrap();
+
+        match signed.call {
+            PermitCall::Erc2612 { signature, .. } => {
+                assert_eq!(
+                    signature.recover(H256::from(hash)).unwrap(),
+                    wallet().address()
+                )
+            }
+            _ => panic!("expected ERC-2612 permit"),
+        }
+        assert_eq!(signed.token, usdc());
+    }
+
+    #[test]
+    fn should_pack_and_decode_erc2612_permit() {
+        let domain = get_token_domain("USD Coin", "2", &U256::from(1), &usdc());
+        let signed = erc2612_permit().sign(&domain, &wallet()).unwrap();
+
+        let packed = signed.build();
+
+        assert_eq!(packed.len(), 20 + 7 * 32);
+        assert_eq!(&packed[0..20], usdc().as_bytes());
+        assert_eq!(TokenPermit::decode(&packed), Ok(signed));
+    }
+
+    #[test]
+    fn should_pack_and_decode_dai_permit() {
+        let dai = Address::from_str("0x6b175474e89094c44da98b954eedeac495271d0f").unwrap();
+        let permit = DaiPermitData {
+            holder: wallet().address(),
+            spender: router_v5(),
+            nonce: U256::zero(),
+            expiry: U256::from(1673548200),
+            allowed: true,
+        };
+
+        let signed = permit
+            .sign(&get_dai_domain(&U256::from(1), &dai), &wallet())
+            .unwrap();
+        let packed = signed.build();
+
+        assert_eq!(packed.len(), 20 + 8 * 32);
+        assert_eq!(TokenPermit::decode(&packed), Ok(signed));
+    }
+
+    #[test]
+    fn should_refuse_signature_v_above_uint8() {
+        let domain = get_token_domain("USD Coin", "2", &U256::from(1), &usdc());
+        let built = erc2612_permit().sign(&domain, &wallet()).unwrap().build();
+        let mut packed = built.to_vec();
+        // high byte of v word, v becomes 256 + v
+        packed[20 + 5 * 32 - 2] = 1;
+
+        assert_eq!(
+            TokenPermit::decode(&packed.into()),
+            Err(format!(
+                "Signature v {} does not fit into uint8",
+                256 + u64::from(built[20 + 5 * 32 - 1])
+            ))
+        );
+    }
+
+    #[test]
+    fn should_refuse_permit_of_unknown_length() {
+        assert_eq!(
+            TokenPermit::decode(&Bytes::from(vec![0; 20 + 32])),
+            Err("Unknown permit length 32".to_string())
+        );
+    }
+}
@@ -0,0 +1,402 @@
+use ethers::{
+    abi::{decode, encode, ParamType, Token},
+    signers::LocalWallet,
+    types::{transaction::eip712::TypedData, Address, Bytes, Signature, U256},
+};
+
+use super::{
+    eip712::{build_permit_single_data, build_permit_transfer_from_data},
+    sign_typed_data,
+    types::{
+        PermitCall, PermitDetails, PermitSingle, PermitTransferFrom, TokenPermissions, TokenPermit,
+    },
+};
+
+// owner and transferFrom(from, to, amount) arguments are prepended by the contracts
+const OWNER_LENGTH: usize = 32;
+const TRANSFER_FROM_ARGS_LENGTH: usize = 3 * 32;
+
+impl PermitSingle {
+    pub fn typed_data(&self, chain_id: &U256) -> TypedData {
+        build_permit_single_data(chain_id, self)
+    }
+
+    // packed permit is applied through IPermit2.permit, order should enable permit2 usage
+    pub fn sign(&self, chain_id: &U256, wallet: &LocalWallet) -> Result<TokenPermit, String> {
+        Ok(TokenPermit {
+            token: self.details.token,
+            call: PermitCall::Permit2 {
+                permit: self.clone(),
+                signature: sign_typed_data(&self.typed_data(chain_id), wallet)?,
+            },
+        })
+    }
+
+    pub(super) fn encode_call(&self, signature: &Signature) -> Vec<u8> {
+        let args = encode(&[
+            Token::Address(Address::zero()),
+            self.to_token(),
+            Token::Bytes(signature.to_vec()),
+        ]);
+
+        args[OWNER_LENGTH..].to_vec()
+    }
+
+    pub(super) fn decode_call(args: &[u8]) -> Result<(Self, Signature), String> {
+        let args = [&[0; OWNER_LENGTH], args].concat();
+        let mut tokens = decode(
+            &[ParamType::Address, Self::param_type(), ParamType::Bytes],
+            &args,
+        )
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .skip(1);
+
+        let mut permit = tokens.next().unwrap().into_tuple().unwrap().into_iter();
+        let mut details = permit.next().unwrap().into_tuple().unwrap().into_iter();
+
+        let permit = PermitSingle {
+            details: PermitDetails {
+                token: details.next().unwrap().into_address().unwrap(),
+                amount: uint160(details.next().unwrap().into_uint().unwrap(), "amount")?,
+                expiration: uint48(details.next().unwrap().into_uint().unwrap(), "expiration")?,
+                nonce: uint48(details.next().unwrap().into_uint().unwrap(), "nonce")?,
+            },
+            spender: permit.next().unwrap().into_address().unwrap(),
+            sig_deadline: permit.next().unwrap().into_uint().unwrap(),
+        };
+
+        Ok((permit, decode_signature(tokens.next().unwrap())?))
+    }
+
+    fn param_type() -> ParamType {
+        ParamType::Tuple(vec![
+            ParamType::Tuple(vec![
+                ParamType::Address,
+                ParamType::Uint(160),
+                ParamType::Uint(48),
+                ParamType::Uint(48),
+            ]),
+            ParamType::Address,
+            ParamType::Uint(256),
+        ])
+    }
+
+    fn to_token(&self) -> Token {
+        Token::Tuple(vec![
+            Token::Tuple(vec![
+                Token::Address(self.details.token),
+                Token::Uint(self.details.amount),
+                Token::Uint(U256::from(self.details.expiration)),
+                Token::Uint(U256::from(self.details.nonce)),
+            ]),
+            Token::Address(self.spender),
+            Token::Uint(self.sig_deadline),
+        ])
+    }
+}
+
+impl PermitTransferFrom {
+    pub fn typed_data(&self, chain_id: &U256) -> TypedData {
+        build_permit_transfer_from_data(chain_id, self)
+    }
+
+    pub fn sign(&self, chain_id: &U256, wallet: &LocalWallet) -> Result<Signature, String> {
+        sign_typed_data(&self.typed_data(chain_id), wallet)
+    }
+
+    // suffix of transferFrom call to Permit2Proxy, which is used as maker asset
+    pub fn build_maker_asset_data(&self, signature: &Signature) -> Bytes {
+        let args = encode(&[
+            Token::Address(Address::zero()),
+            Token::Address(Address::zero()),
+            Token::Uint(U256::zero()),
+            Token::Tuple(vec![
+                Token::Tuple(vec![
+                    Token::Address(self.permitted.token),
+                    Token::Uint(self.permitted.amount),
+                ]),
+                Token::Uint(self.nonce),
+                Token::Uint(self.deadline),
+            ]),
+            Token::Bytes(signature.to_vec()),
+        ]);
+
+        args[TRANSFER_FROM_ARGS_LENGTH..].to_vec().into()
+    }
+
+    // spender is the proxy calling Permit2, so it's not a part of the data
+    pub fn decode_maker_asset_data(
+        maker_asset_data: &Bytes,
+        proxy: &Address,
+    ) -> Result<(Self, Signature), String> {
+        let args = [&[0; TRANSFER_FROM_ARGS_LENGTH], maker_asset_data.as_ref()].concat();
+        let mut tokens = decode(
+            &[
+                ParamType::Address,
+                ParamType::Address,
+                ParamType::Uint(256),
+                ParamType::Tuple(vec![
+                    ParamType::Tuple(vec![ParamType::Address, ParamType::Uint(256)]),
+                    ParamType::Uint(256),
+                    ParamType::Uint(256),
+                ]),
+                ParamType::Bytes,
+            ],
+            &args,
+        )
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .skip(3);
+
+        let mut permit = tokens.next().unwrap().into_tuple().unwrap().into_iter();
+        let mut permitted = permit.next().unwrap().into_tuple().unwrap().into_iter();
+
+        let permit = PermitTransferFrom {
+            permitted: TokenPermissions {
+                token: permitted.next().unwrap().into_address().unwrap(),
+                amount: permitted.next().unwrap().into_uint().unwrap(),
+            },
+            spender: *proxy,
+            nonce: permit.next().unwrap().into_uint().unwrap(),
+            deadline: permit.next().unwrap().into_uint().unwrap(),
+        };
+
+        Ok((permit, decode_signature(tokens.next().unwrap())?))
+    }
+}
+
+// abi decoding doesn't check that values fit into the declared uint size
+fn uint48(value: U256, name: &str) -> Result<u64, String> {
+    if value >= U256::one() << 48 {
+        return Err(format!(
+            "Permit2 {} {} does not fit into uint48",
+            name, value
+        ));
+    }
+
+    Ok(value.as_u64())
+}
+
+fn uint160(value: U256, name: &str) -> Result<U256, String> {
+    if value >= U256::one() << 160 {
+        return Err(format!(
+            "Permit2 {} {} does not fit into uint160",
+            name, value
+        ));
+    }
+
+    Ok(value)
+}
+
+fn decode_signature(token: Token) -> Result<Signature, String> {
+    Signature::try_from(token.into_bytes().unwrap().as_slice()).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::{
+        abi::{encode, Token},
+        signers::Signer,
+        types::{transaction::eip712::Eip712, Address, Bytes, H256, U256},
+        utils::keccak256,
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        permit::{
+            eip712::get_permit2_domain,
+            types::{
+                PermitCall, PermitDetails, PermitSingle, PermitTransferFrom, TokenPermissions,
+                TokenPermit,
+            },
+        },
+        test_utils::{router_v6, usdc, wallet},
+    };
+
+    fn permit_single() -> PermitSingle {
+        PermitSingle {
+            details: PermitDetails {
+                token: usdc(),
+                amount: U256::from(1420000000),
+                expiration: 1673548200,
+                nonce: 1,
+            },
+            spender: router_v6(),
+            sig_deadline: U256::from(1673548200),
+        }
+    }
+
+    fn permit_transfer_from() -> PermitTransferFrom {
+        PermitTransferFrom {
+            permitted: TokenPermissions {
+                token: usdc(),
+                amount: U256::from(1420000000),
+            },
+            spender: Address::from_low_u64_be(1),
+            nonce: U256::from(5),
+            deadline: U256::from(1673548200),
+        }
+    }
+
+    fn h256(hex: &str) -> H256 {
+        H256::from_str(hex).unwrap()
+    }
+
+    #[test]
+    fn should_use_permit2_domain_separator() {
+        assert_eq!(
+            H256::from(get_permit2_domain(&U256::from(1)).separator()),
+            h256("0x866a5aba21966af95d6c7ab78eb2b2fc913915c28be3b9aa07cc04ff903e3f28")
+        );
+    }
+
+    #[test]
+    fn should_hash_permit_single() {
+        let permit = permit_single();
+
+        let details_hash = keccak256(encode(&[
+            Token::FixedBytes(
+                h256("0x65626cad6cb96493bf6f5ebea28756c966f023ab9e8a83a7101849d5573b3678")
+                    .as_bytes()
+                    .to_vec(),
+            ),
+            Token::Address(permit.details.token),
+            Token::Uint(permit.details.amount),
+            Token::Uint(U256::from(permit.details.expiration)),
+            Token::Uint(U256::from(permit.details.nonce)),
+        ]));
+        let struct_hash = keccak256(encode(&[
+            Token::FixedBytes(
+                h256("0xf3841cd1ff0085026a6327b620b67997ce40f282c88a8e905a7a5626e310f3d0")
+                    .as_bytes()
+                    .to_vec(),
+            ),
+            Token::FixedBytes(details_hash.to_vec()),
+            Token::Address(permit.spender),
+            Token::Uint(permit.sig_deadline),
+        ]));
+
+        assert_eq!(
+            H256::from(permit.typed_data(&U256::from(1)).struct_hash().unwrap()),
+            H256::from(struct_hash)
+        );
+    }
+
+    #[test]
+    fn should_hash_permit_transfer_from() {
+        let permit = permit_transfer_from();
+
+        let permitted_hash = keccak256(encode(&[
+            Token::FixedBytes(
+                h256("0x618358ac3db8dc274f0cd8829da7e234bd48cd73c4a740aede1adec9846d06a1")
+                    .as_bytes()
+                    .to_vec(),
+            ),
+            Token::Address(permit.permitted.token),
+            Token::Uint(permit.permitted.amount),
+        ]));
+        let struct_hash = keccak256(encode(&[
+            Token::FixedBytes(
+                h256("0x939c21a48a8dbe3a9a2404a1d46691e4d39f6583d6ec6b35714604c986d80106")
+                    .as_bytes()
+                    .to_vec(),
+            ),
+            Token::FixedBytes(permitted_hash.to_vec()),
+            Token::Address(permit.spender),
+            Token::Uint(permit.nonce),
+            Token::Uint(permit.deadline),
+        ]));
+
+        assert_eq!(
+            H256::from(permit.typed_data(&U256::from(1)).struct_hash().unwrap()),
+            H256::from(struct_hash)
+        );
+    }
+
+    #[test]
+    fn should_pack_and_decode_permit_single() {
+        let signed = permit_single().sign(&U256::from(1), &wallet()).unwrap();
+        let packed = signed.build();
+
+        assert_eq!(packed.len(), 20 + 11 * 32);
+        assert_eq!(TokenPermit::decode(&packed), Ok(signed.clone()));
+
+        match signed.call {
+            PermitCall::Permit2 { permit, signature } => {
+                let hash = permit.typed_data(&U256::from(1)).encode_eip712().unwrap();
+
+                assert_eq!(
+                    signature.recover(H256::from(hash)).unwrap(),
+                    wallet().address()
+                );
+            }
+            _ => panic!("expected Permit2 permit"),
+        }
+    }
+
+    #[test]
+    fn should_refuse_permit_single_out_of_uint_range() {
+        let packed = permit_single()
+            .sign(&U256::from(1), &wallet())
+            .unwrap()
+            .build()
+            .to_vec();
+
+        // lowest byte above uint160 of amount word
+        let mut amount = packed.clone();
+        amount[20 + 32 + 11] = 1;
+
+        assert_eq!(
+            TokenPermit::decode(&amount.into()),
+            Err(format!(
+                "Permit2 amount {} does not fit into uint160",
+                (U256::one() << 160) + U256::from(1420000000)
+            ))
+        );
+
+        // lowest byte above uint48 of expiration and nonce words
+        let mut expiration = packed.clone();
+        expiration[20 + 2 * 32 + 25] = 1;
+
+        assert_eq!(
+            TokenPermit::decode(&expiration.into()),
+            Err(format!(
+                "Permit2 expiration {} does not fit into uint48",
+                (1u64 << 48) + 1673548200
+            ))
+        );
+
+        let mut nonce = packed;
+        nonce[20 + 3 * 32 + 25] = 1;
+
+        assert_eq!(
+            TokenPermit::decode(&nonce.into()),
+            Err(format!(
+                "Permit2 nonce {} does not fit into uint48",
+                (1u64 << 48) + 1
+            ))
+        );
+    }
+
+    #[test]
+    fn should_build_and_decode_maker_asset_data() {
+        let permit = permit_transfer_from();
+        let signature = permit.sign(&U256::from(1), &wallet()).unwrap();
+
+        let maker_asset_data = permit.build_maker_asset_data(&signature);
+
+        // permit (4 words), signature offset, length and 3 words of signature
+        assert_eq!(maker_asset_data.len(), 9 * 32);
+        assert_eq!(
+            Bytes::from(maker_asset_data[4 * 32..5 * 32].to_vec()),
+            Bytes::from(encode(&[Token::Uint(U256::from(8 * 32))]))
+        );
+        assert_eq!(
+            PermitTransferFrom::decode_maker_asset_data(&maker_asset_data, &permit.spender),
+            Ok((permit, signature))
+        );
+    }
+}
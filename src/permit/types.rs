@@ -0,0 +1,84 @@
+use ethers::types::{Address, Signature, U256};
+
+// message of EIP-2612 permit, nonce is not a part of the calldata
+#[derive(Debug, Clone, PartialEq)]
+pub struct Erc2612PermitData {
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+    pub nonce: U256,
+    pub deadline: U256,
+}
+
+// message of DAI permit, which allows unlimited or zero amount
+#[derive(Debug, Clone, PartialEq)]
+pub struct DaiPermitData {
+    pub holder: Address,
+    pub spender: Address,
+    pub nonce: U256,
+    pub expiry: U256,
+    pub allowed: bool,
+}
+
+// arguments of permit call, which are distinguished by length in SafeERC20.safePermit
+#[derive(Debug, Clone, PartialEq)]
+pub enum PermitCall {
+    Erc2612 {
+        owner: Address,
+        spender: Address,
+        value: U256,
+        deadline: U256,
+        signature: Signature,
+    },
+    Dai {
+        holder: Address,
+        spender: Address,
+        nonce: U256,
+        expiry: U256,
+        allowed: bool,
+        signature: Signature,
+    },
+    // IPermit2.permit without owner, which is the order maker
+    Permit2 {
+        permit: PermitSingle,
+        signature: Signature,
+    },
+}
+
+// token address followed by permit call arguments, as stored in limit order permit
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenPermit {
+    pub token: Address,
+    pub call: PermitCall,
+}
+
+// https://github.com/Uniswap/permit2/blob/main/src/interfaces/IAllowanceTransfer.sol
+#[derive(Debug, Clone, PartialEq)]
+pub struct PermitDetails {
+    pub token: Address,
+    pub amount: U256,    // uint160
+    pub expiration: u64, // uint48
+    pub nonce: u64,      // uint48
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PermitSingle {
+    pub details: PermitDetails,
+    pub spender: Address,
+    pub sig_deadline: U256,
+}
+
+// https://github.com/Uniswap/permit2/blob/main/src/interfaces/ISignatureTransfer.sol
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenPermissions {
+    pub token: Address,
+    pub amount: U256,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PermitTransferFrom {
+    pub permitted: TokenPermissions,
+    pub spender: Address, // contract calling permitTransferFrom
+    pub nonce: U256,
+    pub deadline: U256,
+}
@@ -0,0 +1,283 @@
+pub mod types;
+
+use std::str::FromStr;
+
+use ethers::types::{Address, I256, U256};
+
+use crate::{auction_calculator::types::AuctionCalculator, constants::NATIVE_CURRENCY};
+
+use types::{PriceSource, Profitability, ProfitabilityCalculator};
+
+impl<P: PriceSource> ProfitabilityCalculator<P> {
+    pub fn new(calculator: AuctionCalculator, price_source: P, native_token: Address) -> Self {
+        ProfitabilityCalculator {
+            calculator,
+            price_source,
+            native_token,
+            gas_price: U256::zero(),
+            gas_estimate: U256::zero(),
+            access_time: None,
+        }
+    }
+
+    pub fn with_gas(self, gas_price: U256, gas_estimate: U256) -> Self {
+        Self {
+            gas_price,
+            gas_estimate,
+            ..self
+        }
+    }
+
+    pub fn with_access_time(self, access_time: u64) -> Self {
+        Self {
+            access_time: Some(access_time),
+            ..self
+        }
+    }
+
+    // path leads from maker asset to taker asset,
+    // making and taking amounts are the order amounts being filled
+    pub fn evaluate(
+        &self,
+        path: &[Address],
+        making_amount: U256,
+        taking_amount: U256,
+        time: u64,
+    ) -> Result<Profitability, String> {
+        if path.len() < 2 {
+            return Err("Path must contain at least two tokens".to_string());
+        }
+
+        let amount_out = self.price_source.amount_out(path, making_amount)?;
+        let gas_cost = self.gas_cost(path[path.len() - 1])?;
+        let revenue = amount_out.saturating_sub(gas_cost);
+
+        let current_taking_amount = self.taking_amount_at(&taking_amount, time);
+        let break_even_time = self.break_even_time(&taking_amount, &revenue);
+
+        Ok(Profitability {
+            amount_out,
+            gas_cost,
+            taking_amount: current_taking_amount,
+            profit: to_signed(amount_out)?
+                - to_signed(gas_cost)?
+                - to_signed(current_taking_amount)?,
+            break_even_time,
+            // resolvers compete for the order, so waiting past break even only risks losing it
+            best_fill_time: break_even_time
+                .map(|break_even| break_even.max(time).max(self.access_time.unwrap_or(0))),
+        })
+    }
+
+    // gas cost converted to the taker asset
+    pub fn gas_cost(&self, taker_asset: Address) -> Result<U256, String> {
+        let gas_cost = self
+            .gas_price
+            .checked_mul(self.gas_estimate)
+            .ok_or_else(|| "Amount overflow".to_string())?;
+
+        if gas_cost.is_zero()
+            || taker_asset == self.native_token
+            || taker_asset == Address::from_str(NATIVE_CURRENCY).unwrap()
+        {
+            return Ok(gas_cost);
+        }
+
+        self.price_source
+            .amount_out(&[self.native_token, taker_asset], gas_cost)
+    }
+
+    fn taking_amount_at(&self, taking_amount: &U256, time: u64) -> U256 {
+        let rate_bump = self.calculator.calc_rate_bump(time as u32);
+
+        self.calculator
+            .calc_auction_taking_amount(taking_amount, rate_bump)
+    }
+
+    // the earliest time auction taking amount does not exceed revenue,
+    // taking amount never grows during the auction so binary search applies
+    fn break_even_time(&self, taking_amount: &U256, revenue: &U256) -> Option<u64> {
+        let profitable = |time: u64| self.taking_amount_at(taking_amount, time) <= *revenue;
+
+        let mut from = self.calculator.start_time;
+        let mut to = from + self.calculator.duration as u64;
+
+        if !profitable(to) {
+            return None;
+        }
+
+        while from < to {
+            let middle = from + (to - from) / 2;
+
+            if profitable(middle) {
+                to = middle;
+            } else {
+                from = middle + 1;
+            }
+        }
+
+        Some(from)
+    }
+}
+
+fn to_signed(amount: U256) -> Result<I256, String> {
+    I256::try_from(amount).map_err(|_| "Amount overflow".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use ethers::types::{Address, I256, U256};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        auction_calculator::types::AuctionCalculator,
+        test_utils::{usdc, weth},
+    };
+
+    use super::types::{PriceSource, Profitability, ProfitabilityCalculator};
+
+    // fixed rates keyed by path ends, numerator / denominator
+    struct FixedRates(HashMap<(Address, Address), (u64, u64)>);
+
+    impl PriceSource for FixedRates {
+        fn amount_out(&self, path: &[Address], amount_in: U256) -> Result<U256, String> {
+            let &(numerator, denominator) = self
+                .0
+                .get(&(path[0], path[path.len() - 1]))
+                .ok_or("No liquidity")?;
+
+            Ok(amount_in * numerator / denominator)
+        }
+    }
+
+    // 10% rate bump linearly decreasing to zero within 100 seconds
+    fn calculator() -> AuctionCalculator {
+        AuctionCalculator {
+            start_time: 1000,
+            duration: 100,
+            initial_rate_bump: 1_000_000,
+            points: vec![],
+            taker_fee_ratio: U256::zero(),
+        }
+    }
+
+    fn prices(weth_usdc: (u64, u64)) -> FixedRates {
+        FixedRates(HashMap::from([((weth(), usdc()), weth_usdc)]))
+    }
+
+    #[test]
+    fn should_evaluate_profitability() {
+        let price_source = prices((105, 100));
+        let profitability = ProfitabilityCalculator::new(calculator(), &price_source, weth())
+            .with_gas(U256::from(10), U256::from(1000));
+
+        // gas costs 10000 wei, 1 wei is quoted at 1.05 usdc units
+        assert_eq!(
+            profitability
+                .evaluate(
+                    &[weth(), usdc()],
+                    U256::from(1_000_000),
+                    U256::from(1_000_000),
+                    1050
+                )
+                .unwrap(),
+            Profitability {
+                amount_out: U256::from(1_050_000),
+                gas_cost: U256::from(10_500),
+                taking_amount: U256::from(1_050_000),
+                profit: I256::from(-10_500),
+                break_even_time: Some(1061),
+                best_fill_time: Some(1061),
+            }
+        );
+    }
+
+    #[test]
+    fn should_respect_resolver_access_and_current_time() {
+        let price_source = prices((103, 100));
+        let profitability = ProfitabilityCalculator::new(calculator(), &price_source, weth());
+        let evaluate = |profitability: &ProfitabilityCalculator<&FixedRates>, time| {
+            profitability
+                .evaluate(
+                    &[weth(), usdc()],
+                    U256::from(1_000_000),
+                    U256::from(1_000_000),
+                    time,
+                )
+                .unwrap()
+        };
+
+        let at_start = evaluate(&profitability, 1000);
+
+        assert_eq!(at_start.break_even_time, Some(1070));
+        assert_eq!(at_start.best_fill_time, Some(1070));
+        assert_eq!(at_start.profit, I256::from(-70_000));
+
+        let late = evaluate(&profitability, 1090);
+
+        assert_eq!(late.best_fill_time, Some(1090));
+        assert_eq!(late.profit, I256::from(20_000));
+
+        assert_eq!(
+            evaluate(&profitability.clone().with_access_time(1080), 1000).best_fill_time,
+            Some(1080)
+        );
+    }
+
+    #[test]
+    fn should_handle_never_and_always_profitable_orders() {
+        let cheap = prices((99, 100));
+        let expensive = prices((2, 1));
+
+        let never = ProfitabilityCalculator::new(calculator(), &cheap, weth())
+            .evaluate(&[weth(), usdc()], U256::from(100), U256::from(100), 1000)
+            .unwrap();
+        let always = ProfitabilityCalculator::new(calculator(), &expensive, weth())
+            .evaluate(&[weth(), usdc()], U256::from(100), U256::from(100), 900)
+            .unwrap();
+
+        assert_eq!(never.break_even_time, None);
+        assert_eq!(never.best_fill_time, None);
+        assert_eq!(always.break_even_time, Some(1000));
+        assert_eq!(always.best_fill_time, Some(1000));
+    }
+
+    #[test]
+    fn should_fail_without_quote() {
+        let price_source = prices((1, 1));
+        let profitability = ProfitabilityCalculator::new(calculator(), &price_source, weth())
+            .with_gas(U256::one(), U256::one());
+
+        assert_eq!(
+            profitability.evaluate(&[usdc(), weth()], U256::one(), U256::one(), 1000),
+            Err("No liquidity".to_string())
+        );
+        assert_eq!(
+            profitability.evaluate(
+                &[weth(), Address::from_low_u64_be(3)],
+                U256::one(),
+                U256::one(),
+                1000
+            ),
+            Err("No liquidity".to_string())
+        );
+        assert_eq!(
+            profitability.evaluate(&[weth()], U256::one(), U256::one(), 1000),
+            Err("Path must contain at least two tokens".to_string())
+        );
+    }
+
+    #[test]
+    fn should_refuse_overflowing_gas_cost() {
+        let price_source = prices((1, 1));
+        let profitability = ProfitabilityCalculator::new(calculator(), &price_source, weth())
+            .with_gas(U256::MAX, U256::from(2));
+
+        assert_eq!(
+            profitability.gas_cost(usdc()),
+            Err("Amount overflow".to_string())
+        );
+    }
+}
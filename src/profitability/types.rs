@@ -0,0 +1,34 @@
+use ethers::types::{Address, I256, U256};
+
+use crate::auction_calculator::types::AuctionCalculator;
+
+pub trait PriceSource {
+    // amount of the last token of path received for amount_in of the first one
+    fn amount_out(&self, path: &[Address], amount_in: U256) -> Result<U256, String>;
+}
+
+impl<T: PriceSource + ?Sized> PriceSource for &T {
+    fn amount_out(&self, path: &[Address], amount_in: U256) -> Result<U256, String> {
+        (**self).amount_out(path, amount_in)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfitabilityCalculator<P: PriceSource> {
+    pub calculator: AuctionCalculator,
+    pub price_source: P,
+    pub native_token: Address, // wrapped native token gas cost is priced through
+    pub gas_price: U256,
+    pub gas_estimate: U256,
+    pub access_time: Option<u64>, // whitelist allowance of the resolver
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profitability {
+    pub amount_out: U256,    // external quote for the making amount
+    pub gas_cost: U256,      // in taker asset
+    pub taking_amount: U256, // auction taking amount at the evaluated time
+    pub profit: I256,        // amount_out - gas_cost - taking_amount
+    pub break_even_time: Option<u64>,
+    pub best_fill_time: Option<u64>,
+}
@@ -0,0 +1,12 @@
+// https://github.com/1inch/limit-order-protocol/blob/v3.0.1/contracts/OrderMixin.sol
+pub const FILL_ORDER: &str = "fillOrder";
+pub const FILL_ORDER_TO: &str = "fillOrderTo";
+pub const FILL_ORDER_TO_WITH_PERMIT: &str = "fillOrderToWithPermit";
+pub const CANCEL_ORDER: &str = "cancelOrder";
+
+// https://github.com/1inch/limit-order-protocol/blob/v3.0.1/contracts/helpers/NonceManager.sol
+pub const INCREASE_NONCE: &str = "increaseNonce";
+pub const ADVANCE_NONCE: &str = "advanceNonce";
+
+// highest bit of skipPermitAndThresholdAmount
+pub const SKIP_PERMIT_FLAG_BIT: usize = 255;
@@ -0,0 +1,342 @@
+pub mod constants;
+
+use ethers::{
+    abi::{decode, encode, ParamType, Token},
+    types::{Address, Bytes, U256},
+    utils::id,
+};
+
+use crate::limit_order::types::LimitOrderV3Struct;
+
+use constants::*;
+
+// common arguments of fillOrder* functions of ONE_INCH_ROUTER_V5
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillOrderArgs {
+    pub order: LimitOrderV3Struct,
+    pub signature: Bytes,
+    pub interaction: Bytes,
+    pub making_amount: U256,
+    pub taking_amount: U256,
+    pub threshold_amount: U256,
+    pub skip_permit: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RouterCall {
+    FillOrder(FillOrderArgs),
+    FillOrderTo {
+        fill: FillOrderArgs,
+        target: Address,
+    },
+    FillOrderToWithPermit {
+        fill: FillOrderArgs,
+        target: Address,
+        permit: Bytes,
+    },
+    CancelOrder(LimitOrderV3Struct),
+    // invalidates all orders bound to the current nonce of the sender
+    IncreaseNonce,
+    AdvanceNonce(u8),
+}
+
+impl RouterCall {
+    pub fn encode(&self) -> Bytes {
+        let (name, tokens) = match self {
+            RouterCall::FillOrder(fill) => (FILL_ORDER, fill.to_tokens()),
+            RouterCall::FillOrderTo { fill, target } => {
+                let mut tokens = fill.to_tokens();
+                tokens.push(Token::Address(*target));
+
+                (FILL_ORDER_TO, tokens)
+            }
+            RouterCall::FillOrderToWithPermit {
+                fill,
+                target,
+                permit,
+            } => {
+                let mut tokens = fill.to_tokens();
+                tokens.push(Token::Address(*target));
+                tokens.push(Token::Bytes(permit.to_vec()));
+
+                (FILL_ORDER_TO_WITH_PERMIT, tokens)
+            }
+            RouterCall::CancelOrder(order) => (CANCEL_ORDER, vec![order.to_token()]),
+            RouterCall::IncreaseNonce => (INCREASE_NONCE, vec![]),
+            RouterCall::AdvanceNonce(amount) => {
+                (ADVANCE_NONCE, vec![Token::Uint(U256::from(*amount))])
+            }
+        };
+
+        [selector(name, &param_types(name)).to_vec(), encode(&tokens)]
+            .concat()
+            .into()
+    }
+
+    pub fn decode(calldata: &[u8]) -> Result<Self, String> {
+        if calldata.len() < 4 {
+            return Err("Calldata is too short".to_string());
+        }
+
+        let name = [
+            FILL_ORDER,
+            FILL_ORDER_TO,
+            FILL_ORDER_TO_WITH_PERMIT,
+            CANCEL_ORDER,
+            INCREASE_NONCE,
+            ADVANCE_NONCE,
+        ]
+        .into_iter()
+        .find(|name| selector(name, &param_types(name)) == calldata[0..4])
+        .ok_or_else(|| format!("Unknown selector 0x{}", hex::encode(&calldata[0..4])))?;
+
+        let mut tokens = decode(&param_types(name), &calldata[4..])
+            .map_err(|e| e.to_string())?
+            .into_iter();
+
+        match name {
+            CANCEL_ORDER => {
+                return Ok(RouterCall::CancelOrder(LimitOrderV3Struct::from_token(
+                    tokens.next().unwrap(),
+                )?))
+            }
+            INCREASE_NONCE => return Ok(RouterCall::IncreaseNonce),
+            ADVANCE_NONCE => {
+                let amount = tokens.next().unwrap().into_uint().unwrap();
+
+                return Ok(RouterCall::AdvanceNonce(u8::try_from(amount).map_err(
+                    |_| format!("Nonce advance {} does not fit into uint8", amount),
+                )?));
+            }
+            _ => {}
+        }
+
+        let fill = FillOrderArgs::from_tokens(&mut tokens)?;
+
+        if name == FILL_ORDER {
+            return Ok(RouterCall::FillOrder(fill));
+        }
+
+        let target = tokens.next().unwrap().into_address().unwrap();
+
+        if name == FILL_ORDER_TO {
+            return Ok(RouterCall::FillOrderTo { fill, target });
+        }
+
+        Ok(RouterCall::FillOrderToWithPermit {
+            fill,
+            target,
+            permit: tokens.next().unwrap().into_bytes().unwrap().into(),
+        })
+    }
+
+    pub fn order(&self) -> Option<&LimitOrderV3Struct> {
+        match self {
+            RouterCall::FillOrder(fill)
+            | RouterCall::FillOrderTo { fill, .. }
+            | RouterCall::FillOrderToWithPermit { fill, .. } => Some(&fill.order),
+            RouterCall::CancelOrder(order) => Some(order),
+            RouterCall::IncreaseNonce | RouterCall::AdvanceNonce(_) => None,
+        }
+    }
+}
+
+impl FillOrderArgs {
+    fn to_tokens(&self) -> Vec<Token> {
+        let mut skip_permit_and_threshold = self.threshold_amount;
+
+        if self.skip_permit {
+            skip_permit_and_threshold |= U256::one() << SKIP_PERMIT_FLAG_BIT;
+        }
+
+        vec![
+            self.order.to_token(),
+            Token::Bytes(self.signature.to_vec()),
+            Token::Bytes(self.interaction.to_vec()),
+            Token::Uint(self.making_amount),
+            Token::Uint(self.taking_amount),
+            Token::Uint(skip_permit_and_threshold),
+        ]
+    }
+
+    fn from_tokens(tokens: &mut impl Iterator<Item = Token>) -> Result<Self, String> {
+        let order = LimitOrderV3Struct::from_token(tokens.next().unwrap())?;
+        let signature = tokens.next().unwrap().into_bytes().unwrap();
+        let interaction = tokens.next().unwrap().into_bytes().unwrap();
+        let making_amount = tokens.next().unwrap().into_uint().unwrap();
+        let taking_amount = tokens.next().unwrap().into_uint().unwrap();
+        let skip_permit_and_threshold = tokens.next().unwrap().into_uint().unwrap();
+
+        Ok(FillOrderArgs {
+            order,
+            signature: signature.into(),
+            interaction: interaction.into(),
+            making_amount,
+            taking_amount,
+            threshold_amount: skip_permit_and_threshold
+                & ((U256::one() << SKIP_PERMIT_FLAG_BIT) - 1),
+            skip_permit: skip_permit_and_threshold.bit(SKIP_PERMIT_FLAG_BIT),
+        })
+    }
+}
+
+fn param_types(name: &str) -> Vec<ParamType> {
+    match name {
+        CANCEL_ORDER => return vec![LimitOrderV3Struct::param_type()],
+        INCREASE_NONCE => return vec![],
+        ADVANCE_NONCE => return vec![ParamType::Uint(8)],
+        _ => {}
+    }
+
+    let mut types = vec![
+        LimitOrderV3Struct::param_type(),
+        ParamType::Bytes,
+        ParamType::Bytes,
+        ParamType::Uint(256),
+        ParamType::Uint(256),
+        ParamType::Uint(256),
+    ];
+
+    if name == FILL_ORDER_TO || name == FILL_ORDER_TO_WITH_PERMIT {
+        types.push(ParamType::Address);
+    }
+
+    if name == FILL_ORDER_TO_WITH_PERMIT {
+        types.push(ParamType::Bytes);
+    }
+
+    types
+}
+
+fn selector(name: &str, types: &[ParamType]) -> [u8; 4] {
+    let params = types
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+
+    id(format!("{}({})", name, params))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::types::{Address, Bytes, U256};
+    use pretty_assertions::assert_eq;
+
+    use crate::limit_order::types::LimitOrderV3Struct;
+
+    use super::{FillOrderArgs, RouterCall};
+
+    fn order() -> LimitOrderV3Struct {
+        LimitOrderV3Struct {
+            salt: U256::from(1),
+            maker_asset: Address::from_str("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2").unwrap(),
+            taker_asset: Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap(),
+            maker: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+            receiver: Address::zero(),
+            allowed_sender: Address::zero(),
+            making_amount: U256::from(1000000000000000000u64),
+            taking_amount: U256::from(1420000000),
+            offsets: U256::zero(),
+            interactions: Bytes::default(),
+        }
+    }
+
+    fn fill(skip_permit: bool) -> FillOrderArgs {
+        FillOrderArgs {
+            order: order(),
+            signature: Bytes::from(vec![1; 65]),
+            interaction: Bytes::default(),
+            making_amount: U256::zero(),
+            taking_amount: U256::from(1420000000),
+            threshold_amount: U256::from(1000000000000000000u64),
+            skip_permit,
+        }
+    }
+
+    fn target() -> Address {
+        Address::from_str("0x0000000000000000000000000000000000000001").unwrap()
+    }
+
+    #[test]
+    fn should_use_router_v5_selectors() {
+        let selectors = [
+            RouterCall::FillOrder(fill(false)),
+            RouterCall::FillOrderTo {
+                fill: fill(false),
+                target: target(),
+            },
+            RouterCall::FillOrderToWithPermit {
+                fill: fill(false),
+                target: target(),
+                permit: Bytes::default(),
+            },
+            RouterCall::CancelOrder(order()),
+            RouterCall::IncreaseNonce,
+            RouterCall::AdvanceNonce(1),
+        ]
+        .map(|call| hex::encode(&call.encode()[0..4]));
+
+        assert_eq!(
+            selectors,
+            ["62e238bb", "e5d7bde6", "d365c695", "2d9a56f6", "c53a0292", "72c244a8"]
+        );
+    }
+
+    #[test]
+    fn should_roundtrip_calls() {
+        let calls = [
+            RouterCall::FillOrder(fill(true)),
+            RouterCall::FillOrderTo {
+                fill: fill(false),
+                target: target(),
+            },
+            RouterCall::FillOrderToWithPermit {
+                fill: fill(true),
+                target: target(),
+                permit: Bytes::from_str("0xdeadbeef").unwrap(),
+            },
+            RouterCall::CancelOrder(order()),
+            RouterCall::IncreaseNonce,
+            RouterCall::AdvanceNonce(5),
+        ];
+
+        for call in calls {
+            assert_eq!(RouterCall::decode(&call.encode()), Ok(call));
+        }
+    }
+
+    #[test]
+    fn should_set_skip_permit_flag() {
+        let calldata = RouterCall::FillOrder(fill(true)).encode();
+        let threshold = U256::from(&calldata[4 + 5 * 32..4 + 6 * 32]);
+
+        assert!(threshold.bit(255));
+        assert_eq!(
+            threshold & U256::from(u128::MAX),
+            U256::from(1000000000000000000u64)
+        );
+    }
+
+    #[test]
+    fn should_refuse_unknown_selector() {
+        assert_eq!(
+            RouterCall::decode(&[0x12, 0x34, 0x56, 0x78]),
+            Err("Unknown selector 0x12345678".to_string())
+        );
+    }
+
+    #[test]
+    fn should_refuse_nonce_advance_above_uint8() {
+        // uint8 argument is still padded to a word, so it can carry a larger value
+        let mut calldata = RouterCall::AdvanceNonce(1).encode().to_vec();
+        calldata[4 + 30] = 0x01;
+
+        assert_eq!(
+            RouterCall::decode(&calldata),
+            Err("Nonce advance 257 does not fit into uint8".to_string())
+        );
+    }
+}
@@ -1,8 +1,171 @@
-use ethers::types::U256;
-use rand::Rng;
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ethers::{types::U256, utils::keccak256};
+use rand::{rngs::OsRng, RngCore};
+
+use crate::{auction_salt::parser::constants::salt_mask, utils::Maskn};
+
+// all strategies produce values that fit into salt_mask(), so they can be used
+// both for limit order salt and for the random part of auction salt
+pub const SALT_BITS: usize = 144;
+
+pub trait SaltGenerator {
+    fn generate(&self) -> U256;
+}
+
+impl<F> SaltGenerator for F
+where
+    F: Fn() -> U256,
+{
+    fn generate(&self) -> U256 {
+        self()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomSalt;
+
+impl SaltGenerator for RandomSalt {
+    fn generate(&self) -> U256 {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+
+        U256::from(bytes) & salt_mask()
+    }
+}
+
+// keccak256(seed ++ counter), reproducible for the same seed
+#[derive(Debug)]
+pub struct DeterministicSalt {
+    seed: Vec<u8>,
+    counter: AtomicU64,
+}
+
+impl DeterministicSalt {
+    pub fn new(seed: &[u8]) -> Self {
+        Self::with_counter(seed, 0)
+    }
+
+    pub fn with_counter(seed: &[u8], counter: u64) -> Self {
+        Self {
+            seed: seed.to_vec(),
+            counter: AtomicU64::new(counter),
+        }
+    }
+
+    pub fn counter(&self) -> u64 {
+        self.counter.load(Ordering::SeqCst)
+    }
+}
+
+impl SaltGenerator for DeterministicSalt {
+    fn generate(&self) -> U256 {
+        let counter = self.counter.fetch_add(1, Ordering::SeqCst);
+
+        U256::from(keccak256(
+            [self.seed.as_slice(), &counter.to_be_bytes()].concat(),
+        ))
+        .maskn(SALT_BITS)
+    }
+}
+
+// unix time in milliseconds, bumped by one when called more than once per millisecond
+#[derive(Debug, Default)]
+pub struct TimeBasedSalt {
+    last: AtomicU64,
+}
+
+impl TimeBasedSalt {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SaltGenerator for TimeBasedSalt {
+    fn generate(&self) -> U256 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let previous = self
+            .last
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+                Some(now.max(last + 1))
+            })
+            .unwrap();
+
+        U256::from(now.max(previous + 1))
+    }
+}
 
 pub fn build_salt() -> U256 {
-    let rand_int = rand::thread_rng().gen_range(0..1000000);
+    RandomSalt.generate()
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::U256;
+    use pretty_assertions::assert_eq;
+
+    use crate::auction_salt::parser::constants::salt_mask;
+
+    use super::{DeterministicSalt, RandomSalt, SaltGenerator, TimeBasedSalt};
+
+    #[test]
+    fn should_generate_random_salt_within_mask() {
+        let salts: Vec<U256> = (0..16).map(|_| RandomSalt.generate()).collect();
+
+        assert!(salts.iter().all(|salt| salt <= &salt_mask()));
+        assert!(salts.iter().any(|salt| salt.bits() > 64));
+        assert!(salts.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn should_generate_same_sequence_for_same_seed() {
+        let first = DeterministicSalt::new(b"fusion");
+        let second = DeterministicSalt::new(b"fusion");
+
+        let first_salts: Vec<U256> = (0..3).map(|_| first.generate()).collect();
+        let second_salts: Vec<U256> = (0..3).map(|_| second.generate()).collect();
+
+        assert_eq!(first_salts, second_salts);
+        assert_eq!(first.counter(), 3);
+        assert_ne!(first_salts[0], first_salts[1]);
+        assert!(first_salts.iter().all(|salt| salt <= &salt_mask()));
+    }
+
+    #[test]
+    fn should_resume_deterministic_salt_from_counter() {
+        let salt = DeterministicSalt::new(b"fusion");
+        salt.generate();
+
+        assert_eq!(
+            DeterministicSalt::with_counter(b"fusion", 1).generate(),
+            salt.generate()
+        );
+        assert_ne!(
+            DeterministicSalt::new(b"other").generate(),
+            DeterministicSalt::new(b"fusion").generate()
+        );
+    }
+
+    #[test]
+    fn should_generate_strictly_increasing_time_based_salt() {
+        let salt = TimeBasedSalt::new();
+
+        let salts: Vec<U256> = (0..100).map(|_| salt.generate()).collect();
+
+        assert!(salts.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn should_use_closure_as_salt_generator() {
+        let generator = || U256::from(1000);
 
-    U256::from(rand_int)
+        assert_eq!(generator.generate(), U256::from(1000));
+    }
 }
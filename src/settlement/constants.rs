@@ -0,0 +1,8 @@
+// https://github.com/1inch/limit-order-settlement/blob/3c7cf9eacbaf7a60624d7a6f069c59d809f2204a/contracts/Settlement.sol
+pub const SETTLE_ORDERS_SIGNATURE: &str = "settleOrders(bytes)";
+
+pub const CONTINUE_INTERACTION: u8 = 0x00; // _RESOLVE_INTERACTION_CONTINUE
+pub const FINALIZE_INTERACTION: u8 = 0x01; // _FINALIZE_INTERACTION
+
+pub const INTERACTION_TARGET_LENGTH: usize = 20;
+pub const INTERACTION_FLAG_LENGTH: usize = 1;
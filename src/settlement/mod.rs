@@ -0,0 +1,255 @@
+pub mod constants;
+pub mod types;
+
+use ethers::{
+    abi::{decode, encode, ParamType, Token},
+    types::{Address, Bytes},
+    utils::id,
+};
+
+use crate::limit_order::types::LimitOrderV3Struct;
+
+use constants::*;
+use types::*;
+
+pub fn encode_settle_orders(
+    settlement: &Address,
+    fills: &[SettlementFill],
+    resolver_interaction: &ResolverInteraction,
+) -> Bytes {
+    if fills.is_empty() {
+        panic!("At least one order is required to settle");
+    }
+
+    let finalize = [
+        settlement.as_bytes(),
+        &[FINALIZE_INTERACTION],
+        resolver_interaction.target.as_bytes(),
+        &resolver_interaction.data,
+    ]
+    .concat();
+
+    // every order passes the next one to settlement in its interaction,
+    // so the chain is built from the innermost order
+    let mut interaction = finalize;
+    let mut data = vec![];
+
+    for (i, fill) in fills.iter().enumerate().rev() {
+        data = encode_fill(fill, &interaction);
+
+        if i > 0 {
+            interaction = [settlement.as_bytes(), &[CONTINUE_INTERACTION], &data].concat();
+        }
+    }
+
+    [
+        id(SETTLE_ORDERS_SIGNATURE).to_vec(),
+        encode(&[Token::Bytes(data)]),
+    ]
+    .concat()
+    .into()
+}
+
+pub fn decode_settle_orders(calldata: &[u8]) -> Result<SettleOrdersData, String> {
+    if calldata.len() < 4 || calldata[0..4] != id(SETTLE_ORDERS_SIGNATURE) {
+        return Err("Not a settleOrders calldata".to_string());
+    }
+
+    let mut data = decode(&[ParamType::Bytes], &calldata[4..])
+        .map_err(|e| e.to_string())?
+        .remove(0)
+        .into_bytes()
+        .unwrap();
+
+    let mut fills = vec![];
+    let mut settlement = None;
+
+    loop {
+        let (fill, interaction) = decode_fill(&data)?;
+        fills.push(fill);
+
+        if interaction.len() < INTERACTION_TARGET_LENGTH + INTERACTION_FLAG_LENGTH {
+            return Err(format!(
+                "Interaction of order {} is too short",
+                fills.len() - 1
+            ));
+        }
+
+        let target = Address::from_slice(&interaction[0..INTERACTION_TARGET_LENGTH]);
+
+        if *settlement.get_or_insert(target) != target {
+            return Err(format!(
+                "Interaction of order {} targets {:?} instead of settlement",
+                fills.len() - 1,
+                target
+            ));
+        }
+
+        let flag = interaction[INTERACTION_TARGET_LENGTH];
+        let args = &interaction[INTERACTION_TARGET_LENGTH + INTERACTION_FLAG_LENGTH..];
+
+        match flag {
+            CONTINUE_INTERACTION => data = args.to_vec(),
+            FINALIZE_INTERACTION => {
+                if args.len() < INTERACTION_TARGET_LENGTH {
+                    return Err("Resolver interaction is too short".to_string());
+                }
+
+                return Ok(SettleOrdersData {
+                    settlement: target,
+                    fills,
+                    resolver_interaction: ResolverInteraction {
+                        target: Address::from_slice(&args[0..INTERACTION_TARGET_LENGTH]),
+                        data: Bytes::from(args[INTERACTION_TARGET_LENGTH..].to_vec()),
+                    },
+                });
+            }
+            _ => return Err(format!("Unknown interaction flag {}", flag)),
+        }
+    }
+}
+
+fn fill_param_types() -> Vec<ParamType> {
+    vec![
+        LimitOrderV3Struct::param_type(),
+        ParamType::Bytes,
+        ParamType::Bytes,
+        ParamType::Uint(256),
+        ParamType::Uint(256),
+        ParamType::Uint(256),
+        ParamType::Address,
+    ]
+}
+
+// same as fillOrderTo arguments without selector
+fn encode_fill(fill: &SettlementFill, interaction: &[u8]) -> Vec<u8> {
+    encode(&[
+        fill.order.to_token(),
+        Token::Bytes(fill.signature.to_vec()),
+        Token::Bytes(interaction.to_vec()),
+        Token::Uint(fill.making_amount),
+        Token::Uint(fill.taking_amount),
+        Token::Uint(fill.threshold_amount),
+        Token::Address(fill.target),
+    ])
+}
+
+fn decode_fill(data: &[u8]) -> Result<(SettlementFill, Vec<u8>), String> {
+    let mut tokens = decode(&fill_param_types(), data)
+        .map_err(|e| e.to_string())?
+        .into_iter();
+    let mut next = || tokens.next().unwrap();
+
+    let order = LimitOrderV3Struct::from_token(next())?;
+    let signature = next().into_bytes().unwrap();
+    let interaction = next().into_bytes().unwrap();
+
+    Ok((
+        SettlementFill {
+            order,
+            signature: signature.into(),
+            making_amount: next().into_uint().unwrap(),
+            taking_amount: next().into_uint().unwrap(),
+            threshold_amount: next().into_uint().unwrap(),
+            target: next().into_address().unwrap(),
+        },
+        interaction,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ethers::{
+        abi::{decode, ParamType},
+        types::{Address, Bytes, U256},
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::limit_order::types::LimitOrderV3Struct;
+
+    use super::{
+        decode_settle_orders, encode_settle_orders,
+        types::{ResolverInteraction, SettlementFill},
+    };
+
+    fn settlement() -> Address {
+        Address::from_str("0xa88800cd213da5ae406ce248380802bd53b47647").unwrap()
+    }
+
+    fn fill(salt: u64) -> SettlementFill {
+        SettlementFill {
+            order: LimitOrderV3Struct {
+                salt: U256::from(salt),
+                maker_asset: Address::from_str("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")
+                    .unwrap(),
+                taker_asset: Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")
+                    .unwrap(),
+                maker: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+                receiver: Address::zero(),
+                allowed_sender: settlement(),
+                making_amount: U256::from(1000000000000000000u64),
+                taking_amount: U256::from(1420000000),
+                offsets: U256::from(34) << 224,
+                interactions: Bytes::from_str(
+                    "0x000c004e200000000000000000219ab540356cbb839cbe05303d7705faf486570009",
+                )
+                .unwrap(),
+            },
+            signature: Bytes::from(vec![salt as u8; 65]),
+            making_amount: U256::from(1000000000000000000u64),
+            taking_amount: U256::zero(),
+            threshold_amount: U256::from(1420000000),
+            target: settlement(),
+        }
+    }
+
+    fn resolver_interaction() -> ResolverInteraction {
+        ResolverInteraction {
+            target: Address::from_str("0x0000000000000000000000000000000000000001").unwrap(),
+            data: Bytes::from_str("0xdeadbeef").unwrap(),
+        }
+    }
+
+    #[test]
+    fn should_encode_single_order_settlement() {
+        let calldata = encode_settle_orders(&settlement(), &[fill(1)], &resolver_interaction());
+
+        assert_eq!(calldata[0..4].to_vec(), vec![0x09, 0x65, 0xd0, 0x4b]);
+
+        let data = decode(&[ParamType::Bytes], &calldata[4..])
+            .unwrap()
+            .remove(0)
+            .into_bytes()
+            .unwrap();
+        let interaction_offset = U256::from(&data[64..96]).as_usize();
+        let interaction_len = U256::from(&data[interaction_offset..interaction_offset + 32]);
+
+        assert_eq!(interaction_len, U256::from(20 + 1 + 20 + 4));
+        assert_eq!(
+            Bytes::from(data[interaction_offset + 32..interaction_offset + 32 + 45].to_vec()),
+            Bytes::from_str("0xa88800cd213da5ae406ce248380802bd53b47647010000000000000000000000000000000000000001deadbeef").unwrap()
+        );
+    }
+
+    #[test]
+    fn should_decode_chained_settlement() {
+        let fills = vec![fill(1), fill(2), fill(3)];
+
+        let calldata = encode_settle_orders(&settlement(), &fills, &resolver_interaction());
+        let decoded = decode_settle_orders(&calldata).unwrap();
+
+        assert_eq!(decoded.settlement, settlement());
+        assert_eq!(decoded.fills, fills);
+        assert_eq!(decoded.resolver_interaction, resolver_interaction());
+    }
+
+    #[test]
+    fn should_refuse_foreign_calldata() {
+        assert_eq!(
+            decode_settle_orders(&[0x12, 0x34, 0x56, 0x78]),
+            Err("Not a settleOrders calldata".to_string())
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use ethers::types::{Address, Bytes, U256};
+
+use crate::limit_order::types::LimitOrderV3Struct;
+
+// arguments of fillOrderTo for a single order in the settlement chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettlementFill {
+    pub order: LimitOrderV3Struct,
+    pub signature: Bytes,
+    pub making_amount: U256,
+    pub taking_amount: U256,
+    pub threshold_amount: U256,
+    pub target: Address,
+}
+
+// resolver contract called by settlement after all orders are filled
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolverInteraction {
+    pub target: Address,
+    pub data: Bytes,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettleOrdersData {
+    pub settlement: Address,
+    pub fills: Vec<SettlementFill>,
+    pub resolver_interaction: ResolverInteraction,
+}
@@ -0,0 +1,547 @@
+pub mod types;
+
+use std::collections::HashMap;
+
+use ethers::{
+    abi::{encode, Token},
+    types::{Address, Bytes, H256, U256},
+    utils::keccak256,
+};
+use revm::{
+    primitives::{
+        self as evm, AccountInfo, Bytecode, ExecutionResult, Log, Output, ResultAndState, State,
+        TransactTo, KECCAK_EMPTY,
+    },
+    Evm, InMemoryDB,
+};
+
+use crate::{
+    router::{FillOrderArgs, RouterCall},
+    settlement::{
+        encode_settle_orders,
+        types::{ResolverInteraction, SettlementFill},
+    },
+};
+
+use types::{AccountSnapshot, SimulationResult, StateSnapshot, Transfer};
+
+pub const DEFAULT_GAS_LIMIT: u64 = 30_000_000;
+
+const TRANSFER_EVENT: &str = "Transfer(address,address,uint256)";
+
+// executes calls in an embedded EVM without changing the snapshot,
+// contracts code (router, settlement, tokens) should be a part of the snapshot
+pub struct FillSimulator {
+    db: InMemoryDB,
+    block_number: u64,
+    timestamp: u64,
+    chain_id: u64,
+    gas_limit: u64,
+}
+
+impl FillSimulator {
+    pub fn new(snapshot: &StateSnapshot) -> Self {
+        let mut db = InMemoryDB::default();
+
+        for (address, account) in &snapshot.accounts {
+            let address = to_evm_address(address);
+
+            db.insert_account_info(
+                address,
+                AccountInfo {
+                    balance: to_evm_u256(&account.balance),
+                    nonce: account.nonce,
+                    code_hash: KECCAK_EMPTY,
+                    code: Some(Bytecode::new_raw(account.code.to_vec().into())),
+                },
+            );
+
+            for (slot, value) in &account.storage {
+                db.insert_account_storage(address, to_evm_u256(slot), to_evm_u256(value))
+                    .unwrap();
+            }
+        }
+
+        FillSimulator {
+            db,
+            block_number: snapshot.block_number,
+            timestamp: snapshot.timestamp,
+            chain_id: snapshot.chain_id,
+            gas_limit: DEFAULT_GAS_LIMIT,
+        }
+    }
+
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = gas_limit;
+
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+
+        self
+    }
+
+    pub fn simulate_call(
+        &mut self,
+        from: &Address,
+        to: &Address,
+        calldata: &Bytes,
+    ) -> Result<SimulationResult, String> {
+        let (block_number, timestamp, chain_id, gas_limit) = (
+            self.block_number,
+            self.timestamp,
+            self.chain_id,
+            self.gas_limit,
+        );
+
+        let mut evm = Evm::builder()
+            .with_db(&mut self.db)
+            .modify_cfg_env(|cfg| cfg.chain_id = chain_id)
+            .modify_block_env(|block| {
+                block.number = evm::U256::from(block_number);
+                block.timestamp = evm::U256::from(timestamp);
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = to_evm_address(from);
+                tx.transact_to = TransactTo::Call(to_evm_address(to));
+                tx.data = calldata.to_vec().into();
+                tx.gas_limit = gas_limit;
+            })
+            .build();
+
+        let ResultAndState { result, state } = evm
+            .transact()
+            .map_err(|e| format!("Simulation failed: {:?}", e))?;
+
+        Ok(match result {
+            ExecutionResult::Success {
+                gas_used,
+                logs,
+                output,
+                ..
+            } => SimulationResult {
+                success: true,
+                gas_used,
+                transfers: logs.iter().filter_map(parse_transfer).collect(),
+                output: match output {
+                    Output::Call(data) => data.to_vec().into(),
+                    Output::Create(data, _) => data.to_vec().into(),
+                },
+                storage: changed_storage(&state),
+            },
+            ExecutionResult::Revert { gas_used, output } => SimulationResult {
+                success: false,
+                gas_used,
+                transfers: vec![],
+                output: output.to_vec().into(),
+                storage: HashMap::new(),
+            },
+            ExecutionResult::Halt { gas_used, .. } => SimulationResult {
+                success: false,
+                gas_used,
+                transfers: vec![],
+                output: Bytes::default(),
+                storage: HashMap::new(),
+            },
+        })
+    }
+
+    // fillOrderTo of router called directly by taker, router code has to be
+    // a part of the snapshot together with the tokens it calls
+    pub fn simulate_fill(
+        &mut self,
+        taker: &Address,
+        router: &Address,
+        fill: &FillOrderArgs,
+    ) -> Result<SimulationResult, String> {
+        let calldata = RouterCall::FillOrderTo {
+            fill: fill.clone(),
+            target: *taker,
+        }
+        .encode();
+
+        self.simulate_call(taker, router, &calldata)
+    }
+
+    // settleOrders of settlement contract called by resolver
+    pub fn simulate_settlement(
+        &mut self,
+        resolver: &Address,
+        settlement: &Address,
+        fills: &[SettlementFill],
+        resolver_interaction: &ResolverInteraction,
+    ) -> Result<SimulationResult, String> {
+        let calldata = encode_settle_orders(settlement, fills, resolver_interaction);
+
+        self.simulate_call(resolver, settlement, &calldata)
+    }
+}
+
+impl AccountSnapshot {
+    // sets balance of ERC20 token which stores balances mapping at `slot`
+    pub fn with_erc20_balance(mut self, holder: &Address, slot: u64, balance: U256) -> Self {
+        self.storage
+            .insert(mapping_slot(holder, U256::from(slot)), balance);
+
+        self
+    }
+
+    pub fn with_erc20_allowance(
+        mut self,
+        owner: &Address,
+        spender: &Address,
+        slot: u64,
+        allowance: U256,
+    ) -> Self {
+        let owner_slot = mapping_slot(owner, U256::from(slot));
+        self.storage
+            .insert(mapping_slot(spender, owner_slot), allowance);
+
+        self
+    }
+}
+
+// storage slot of mapping(address => ...) value
+pub fn mapping_slot(key: &Address, slot: U256) -> U256 {
+    U256::from(keccak256(encode(&[
+        Token::Address(*key),
+        Token::Uint(slot),
+    ])))
+}
+
+fn parse_transfer(log: &Log) -> Option<Transfer> {
+    let topics = log.data.topics();
+
+    if topics.len() != 3 || topics[0].0 != keccak256(TRANSFER_EVENT) || log.data.data.len() != 32 {
+        return None;
+    }
+
+    Some(Transfer {
+        token: Address::from_slice(log.address.as_slice()),
+        from: Address::from(H256::from_slice(topics[1].as_slice())),
+        to: Address::from(H256::from_slice(topics[2].as_slice())),
+        amount: U256::from(log.data.data.as_ref()),
+    })
+}
+
+fn changed_storage(state: &State) -> HashMap<Address, HashMap<U256, U256>> {
+    state
+        .iter()
+        .map(|(address, account)| {
+            (
+                Address::from_slice(address.as_slice()),
+                account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| slot.is_changed())
+                    .map(|(key, slot)| (from_evm_u256(key), from_evm_u256(&slot.present_value)))
+                    .collect::<HashMap<_, _>>(),
+            )
+        })
+        .filter(|(_, storage)| !storage.is_empty())
+        .collect()
+}
+
+fn to_evm_address(address: &Address) -> evm::Address {
+    evm::Address::from_slice(address.as_bytes())
+}
+
+fn to_evm_u256(value: &U256) -> evm::U256 {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+
+    evm::U256::from_be_bytes(bytes)
+}
+
+fn from_evm_u256(value: &evm::U256) -> U256 {
+    U256::from_big_endian(&value.to_be_bytes::<32>())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, str::FromStr};
+
+    use ethers::{
+        abi::{encode, Token},
+        signers::Signer,
+        types::{transaction::eip712::Eip712, Address, Bytes, H256, U256},
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        limit_order::{types::LimitOrderV3Struct, LimitOrder},
+        router::FillOrderArgs,
+        test_utils::wallet,
+    };
+
+    use super::{
+        mapping_slot,
+        types::{AccountSnapshot, StateSnapshot, Transfer},
+        FillSimulator,
+    };
+
+    // emits Transfer(caller, this, calldataload(4)) and stops
+    const TRANSFER_EMITTER: &str = "0x600435600052\
+        30337fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef\
+        60206000a300";
+    // returns sload(calldataload(4))
+    const STORAGE_READER: &str = "0x6004355460005260206000f3";
+    // reverts with empty data
+    const REVERTER: &str = "0x60006000fd";
+    // minimal ERC20 with balances mapping at slot 0 and allowances at slot 1,
+    // implements only transferFrom (with Transfer event) and balanceOf
+    const ERC20: &str = "0x60003560e01c806323b872dd1461003b57806370a0823114610021575b600080fd\
+        5b600435600052600060205260406000205460005260206000f35b60043560005260\
+        016020526040600020602052336000526040600020805460443580821061001c5790\
+        03905560043560005260006020526040600020805460443580821061001c57900390\
+        556024356000526000602052604060002080546044350190556044356000526024356004\
+        357fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef\
+        60206000a3600160005260206000f3";
+    // stands in for the router: decodes fillOrderTo calldata, pulls
+    // takingAmount = order.takingAmount * makingAmount / order.makingAmount
+    // of taker asset from the caller to the maker, sends makingAmount of maker
+    // asset from the maker to the target and returns (makingAmount, takingAmount),
+    // signature and predicates are not checked
+    const FILL_EXECUTOR: &str =
+        "0x6004356004018060c001358160e0013560643502046323b872dd60e01b600052\
+        336004528160600135602452806044526000600060646000600086604001355af115\
+        61007e57816060013560045260c4356024526064356044526000600060646000600086\
+        602001355af11561007e5760643560005260205260406000f35b600080fd";
+
+    fn address(n: u64) -> Address {
+        Address::from_low_u64_be(n)
+    }
+
+    fn contract(code: &str) -> AccountSnapshot {
+        AccountSnapshot {
+            code: Bytes::from_str(code).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    fn call_with_word(word: U256) -> Bytes {
+        [vec![0x12, 0x34, 0x56, 0x78], encode(&[Token::Uint(word)])]
+            .concat()
+            .into()
+    }
+
+    fn simulator(accounts: Vec<(Address, AccountSnapshot)>) -> FillSimulator {
+        FillSimulator::new(&StateSnapshot {
+            accounts: HashMap::from_iter(accounts),
+            block_number: 16390000,
+            timestamp: 1673548200,
+            chain_id: 1,
+        })
+    }
+
+    #[test]
+    fn should_collect_transfers_and_gas() {
+        let emitter = address(0x1000);
+        let mut simulator = simulator(vec![(emitter, contract(TRANSFER_EMITTER))]);
+
+        let result = simulator
+            .simulate_call(
+                &address(1),
+                &emitter,
+                &call_with_word(U256::from(1420000000)),
+            )
+            .unwrap();
+
+        assert!(result.success);
+        assert!(result.gas_used > 21000);
+        assert_eq!(
+            result.transfers,
+            vec![Transfer {
+                token: emitter,
+                from: address(1),
+                to: emitter,
+                amount: U256::from(1420000000),
+            }]
+        );
+    }
+
+    #[test]
+    fn should_fill_signed_order() {
+        let router = address(0x1000);
+        let maker_token = address(0x2000);
+        let taker_token = address(0x3000);
+        let wallet = wallet();
+        let maker = wallet.address();
+        let taker = address(2);
+
+        let order = LimitOrderV3Struct {
+            salt: U256::from(1),
+            maker_asset: maker_token,
+            taker_asset: taker_token,
+            maker,
+            receiver: Address::zero(),
+            allowed_sender: Address::zero(),
+            making_amount: U256::from(100),
+            taking_amount: U256::from(200),
+            offsets: U256::zero(),
+            interactions: Bytes::default(),
+        };
+        let hash = LimitOrder::get_typed_data_static(&order, None)
+            .encode_eip712()
+            .unwrap();
+        let signature = wallet.sign_hash(H256::from(hash));
+        let fill = FillOrderArgs {
+            order,
+            signature: signature.to_vec().into(),
+            interaction: Bytes::default(),
+            making_amount: U256::from(40),
+            taking_amount: U256::zero(),
+            threshold_amount: U256::from(80),
+            skip_permit: false,
+        };
+
+        let mut simulator = simulator(vec![
+            (router, contract(FILL_EXECUTOR)),
+            (
+                maker_token,
+                contract(ERC20)
+                    .with_erc20_balance(&maker, 0, U256::from(100))
+                    .with_erc20_allowance(&maker, &router, 1, U256::from(100)),
+            ),
+            (
+                taker_token,
+                contract(ERC20)
+                    .with_erc20_balance(&taker, 0, U256::from(500))
+                    .with_erc20_allowance(&taker, &router, 1, U256::from(500)),
+            ),
+        ]);
+
+        let result = simulator.simulate_fill(&taker, &router, &fill).unwrap();
+
+        assert!(result.success);
+        assert_eq!(
+            result.output,
+            Bytes::from(encode(&[
+                Token::Uint(U256::from(40)),
+                Token::Uint(U256::from(80))
+            ]))
+        );
+        assert_eq!(
+            result.transfers,
+            vec![
+                Transfer {
+                    token: taker_token,
+                    from: taker,
+                    to: maker,
+                    amount: U256::from(80),
+                },
+                Transfer {
+                    token: maker_token,
+                    from: maker,
+                    to: taker,
+                    amount: U256::from(40),
+                },
+            ]
+        );
+
+        let balance = |token: &Address, holder: &Address| {
+            result.storage[token]
+                .get(&mapping_slot(holder, U256::zero()))
+                .copied()
+        };
+        assert_eq!(balance(&maker_token, &maker), Some(U256::from(60)));
+        assert_eq!(balance(&maker_token, &taker), Some(U256::from(40)));
+        assert_eq!(balance(&taker_token, &taker), Some(U256::from(420)));
+        assert_eq!(balance(&taker_token, &maker), Some(U256::from(80)));
+    }
+
+    #[test]
+    fn should_not_fill_without_allowance() {
+        let router = address(0x1000);
+        let maker_token = address(0x2000);
+        let taker_token = address(0x3000);
+        let maker = address(1);
+        let taker = address(2);
+        let fill = FillOrderArgs {
+            order: LimitOrderV3Struct {
+                salt: U256::from(1),
+                maker_asset: maker_token,
+                taker_asset: taker_token,
+                maker,
+                receiver: Address::zero(),
+                allowed_sender: Address::zero(),
+                making_amount: U256::from(100),
+                taking_amount: U256::from(200),
+                offsets: U256::zero(),
+                interactions: Bytes::default(),
+            },
+            signature: Bytes::from(vec![1; 65]),
+            interaction: Bytes::default(),
+            making_amount: U256::from(100),
+            taking_amount: U256::zero(),
+            threshold_amount: U256::from(200),
+            skip_permit: false,
+        };
+        let mut simulator = simulator(vec![
+            (router, contract(FILL_EXECUTOR)),
+            (
+                maker_token,
+                contract(ERC20).with_erc20_balance(&maker, 0, U256::from(100)),
+            ),
+            (
+                taker_token,
+                contract(ERC20)
+                    .with_erc20_balance(&taker, 0, U256::from(200))
+                    .with_erc20_allowance(&taker, &router, 1, U256::from(200)),
+            ),
+        ]);
+
+        let result = simulator.simulate_fill(&taker, &router, &fill).unwrap();
+
+        assert!(!result.success);
+        assert_eq!(result.transfers, vec![]);
+        assert!(result.storage.is_empty());
+    }
+
+    #[test]
+    fn should_report_reverted_call() {
+        let reverter = address(0x1000);
+        let mut simulator = simulator(vec![(reverter, contract(REVERTER))]);
+
+        let result = simulator
+            .simulate_call(&address(1), &reverter, &Bytes::default())
+            .unwrap();
+
+        assert!(!result.success);
+        assert_eq!(result.transfers, vec![]);
+    }
+
+    #[test]
+    fn should_seed_erc20_storage() {
+        let token = address(0x1000);
+        let holder = address(1);
+        let mut simulator = simulator(vec![(
+            token,
+            contract(STORAGE_READER).with_erc20_balance(&holder, 0, U256::from(100)),
+        )]);
+
+        let result = simulator
+            .simulate_call(
+                &holder,
+                &token,
+                &call_with_word(mapping_slot(&holder, U256::zero())),
+            )
+            .unwrap();
+
+        assert_eq!(
+            result.output,
+            Bytes::from(encode(&[Token::Uint(U256::from(100))]))
+        );
+    }
+
+    #[test]
+    fn should_compute_mapping_slot() {
+        assert_eq!(
+            mapping_slot(&Address::zero(), U256::zero()),
+            U256::from_str("0xad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5")
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+
+use ethers::types::{Address, Bytes, U256};
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AccountSnapshot {
+    pub balance: U256,
+    pub nonce: u64,
+    pub code: Bytes,
+    pub storage: HashMap<U256, U256>,
+}
+
+// state the fill is executed against, e.g. fetched with eth_getProof or prestate tracer
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StateSnapshot {
+    pub accounts: HashMap<Address, AccountSnapshot>,
+    pub block_number: u64,
+    pub timestamp: u64,
+    pub chain_id: u64,
+}
+
+// ERC20 Transfer event emitted during execution
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transfer {
+    pub token: Address,
+    pub from: Address,
+    pub to: Address,
+    pub amount: U256,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationResult {
+    pub success: bool,
+    pub gas_used: u64,
+    pub transfers: Vec<Transfer>,
+    // return data or revert reason
+    pub output: Bytes,
+    // storage slots changed by a successful call, e.g. token balances after the fill
+    pub storage: HashMap<Address, HashMap<U256, U256>>,
+}
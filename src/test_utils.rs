@@ -0,0 +1,251 @@
+use std::str::FromStr;
+
+use ethers::{
+    signers::LocalWallet,
+    types::{Address, Bytes, U256},
+};
+
+use crate::{
+    auction_salt::types::{AuctionSalt, AuctionSaltData},
+    auction_suffix::{
+        parser::types::{AuctionPoint, AuctionWhitelistItem},
+        types::{AuctionSuffix, PostInteractionData, SettlementSuffixData},
+        whitelist::WhitelistBuilder,
+    },
+    constants::{
+        get_network_address, NetworkEnum, ONE_INCH_ROUTER_V5, ONE_INCH_ROUTER_V6,
+        SETTLEMENT_CONTRACT_ADDRESS_MAP,
+    },
+    limit_order::{
+        predicate::Predicate, types::LimitOrderV3Struct, InteractionsData, LimitOrder,
+        OrderInfoData,
+    },
+};
+
+pub fn settlement() -> Address {
+    get_network_address(&SETTLEMENT_CONTRACT_ADDRESS_MAP, NetworkEnum::ETHEREUM)
+}
+
+pub fn weth() -> Address {
+    Address::from_str("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2").unwrap()
+}
+
+pub fn usdc() -> Address {
+    Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap()
+}
+
+pub fn router_v5() -> Address {
+    Address::from_str(ONE_INCH_ROUTER_V5).unwrap()
+}
+
+pub fn router_v6() -> Address {
+    Address::from_str(ONE_INCH_ROUTER_V6).unwrap()
+}
+
+// first default anvil/hardhat account
+pub fn wallet() -> LocalWallet {
+    LocalWallet::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+        .unwrap()
+}
+
+// fusion order selling 1 WETH for 1420 USDC on ethereum,
+// auction starts at 1673548149 and lasts 180 seconds with 5% initial rate bump,
+// resolvers 0x..01 and 0x..02 are allowed at 1673548200 and 1673548230,
+// anyone is allowed after 1673548300
+#[derive(Debug, Clone, PartialEq)]
+pub struct FusionOrderBuilder {
+    maker: Address,
+    maker_asset: Address,
+    taker_asset: Address,
+    making_amount: U256,
+    taking_amount: U256,
+    salt: u64,
+    auction_start_time: u64,
+    duration: u32,
+    initial_rate_bump: u32,
+    points: Vec<AuctionPoint>,
+    whitelist: Vec<AuctionWhitelistItem>,
+    public_resolving_deadline: u32,
+    predicate: Option<Predicate>,
+    nonce: Option<u64>,
+    allowed_sender: Address,
+    settlement: Address,
+}
+
+impl Default for FusionOrderBuilder {
+    fn default() -> Self {
+        FusionOrderBuilder {
+            maker: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+            maker_asset: weth(),
+            taker_asset: usdc(),
+            making_amount: U256::from(1000000000000000000u64),
+            taking_amount: U256::from(1420000000),
+            salt: 1000,
+            auction_start_time: 1673548149,
+            duration: 180,
+            initial_rate_bump: 50000,
+            points: vec![AuctionPoint {
+                coefficient: 20000,
+                delay: 12,
+            }],
+            whitelist: WhitelistBuilder::new(1673548200)
+                .with_step(30)
+                .with_resolvers(&[Address::from_low_u64_be(1), Address::from_low_u64_be(2)])
+                .build()
+                .unwrap(),
+            public_resolving_deadline: 1673548300,
+            predicate: None,
+            nonce: None,
+            allowed_sender: settlement(),
+            settlement: settlement(),
+        }
+    }
+}
+
+impl FusionOrderBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_maker(self, maker: Address) -> Self {
+        Self { maker, ..self }
+    }
+
+    pub fn with_assets(self, maker_asset: Address, taker_asset: Address) -> Self {
+        Self {
+            maker_asset,
+            taker_asset,
+            ..self
+        }
+    }
+
+    pub fn with_amounts(self, making_amount: U256, taking_amount: U256) -> Self {
+        Self {
+            making_amount,
+            taking_amount,
+            ..self
+        }
+    }
+
+    // low bits of the salt, orders differing only in it have different hashes
+    pub fn with_salt(self, salt: u64) -> Self {
+        Self { salt, ..self }
+    }
+
+    pub fn with_auction(self, start_time: u64, duration: u32, initial_rate_bump: u32) -> Self {
+        Self {
+            auction_start_time: start_time,
+            duration,
+            initial_rate_bump,
+            ..self
+        }
+    }
+
+    pub fn with_points(self, points: Vec<AuctionPoint>) -> Self {
+        Self { points, ..self }
+    }
+
+    pub fn with_whitelist(self, start_time: u32, step: u32, resolvers: &[Address]) -> Self {
+        Self {
+            whitelist: WhitelistBuilder::new(start_time)
+                .with_step(step)
+                .with_resolvers(resolvers)
+                .build()
+                .unwrap(),
+            ..self
+        }
+    }
+
+    pub fn with_public_resolving_deadline(self, public_resolving_deadline: u32) -> Self {
+        Self {
+            public_resolving_deadline,
+            ..self
+        }
+    }
+
+    pub fn with_predicate(self, predicate: Predicate) -> Self {
+        Self {
+            predicate: Some(predicate),
+            ..self
+        }
+    }
+
+    pub fn with_allowed_sender(self, allowed_sender: Address) -> Self {
+        Self {
+            allowed_sender,
+            ..self
+        }
+    }
+
+    // post interaction target
+    pub fn with_settlement(self, settlement: Address) -> Self {
+        Self { settlement, ..self }
+    }
+
+    pub fn with_nonce(self, nonce: u64) -> Self {
+        Self {
+            nonce: Some(nonce),
+            ..self
+        }
+    }
+
+    pub fn auction_salt(&self) -> AuctionSalt {
+        AuctionSalt::new(
+            AuctionSaltData {
+                auction_start_time: self.auction_start_time,
+                initial_rate_bump: self.initial_rate_bump,
+                duration: self.duration,
+                bank_fee: U256::zero(),
+                salt: Some(U256::from(self.salt)),
+            },
+            &|| U256::zero(),
+        )
+    }
+
+    pub fn suffix(&self) -> AuctionSuffix {
+        AuctionSuffix::new(SettlementSuffixData {
+            points: self.points.clone(),
+            whitelist: self.whitelist.clone(),
+            public_resolving_deadline: Some(self.public_resolving_deadline),
+            fee: None,
+        })
+    }
+
+    pub fn post_interaction(&self) -> Bytes {
+        PostInteractionData {
+            interaction: Bytes::default(),
+            suffix: self.suffix(),
+        }
+        .build(&self.settlement)
+    }
+
+    pub fn build_limit_order(&self) -> LimitOrder {
+        let order = LimitOrder::new(
+            &OrderInfoData {
+                maker_asset: self.maker_asset,
+                taker_asset: self.taker_asset,
+                making_amount: self.making_amount,
+                taking_amount: self.taking_amount,
+                maker: self.maker,
+                salt: Some(U256::from_dec_str(&self.auction_salt().build()).unwrap()),
+                allowed_sender: Some(self.allowed_sender),
+                receiver: None,
+            },
+            Some(&InteractionsData {
+                predicate: self.predicate.as_ref().map(|p| p.build()),
+                post_interaction: Some(self.post_interaction()),
+                ..Default::default()
+            }),
+            &|| U256::zero(),
+        );
+
+        match self.nonce {
+            Some(nonce) => order.with_nonce(nonce),
+            None => order,
+        }
+    }
+
+    pub fn build(&self) -> LimitOrderV3Struct {
+        self.build_limit_order().build()
+    }
+}
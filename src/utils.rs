@@ -13,6 +13,21 @@ pub fn to_sec<T: Into<u64>>(time: T) -> u64 {
     t / 1000
 }
 
+// accepts both decimal and 0x prefixed hex numbers
+pub fn parse_u256(value: &str) -> Result<U256, String> {
+    let value = value.trim();
+
+    let parsed = if value.is_empty() {
+        None
+    } else if value.starts_with(ZX) {
+        U256::from_str_radix(trim_0x(value), 16).ok()
+    } else {
+        U256::from_dec_str(value).ok()
+    };
+
+    parsed.ok_or_else(|| format!("Invalid number {:?}", value))
+}
+
 pub fn trim_0x(data: &str) -> &str {
     if data.starts_with(ZX) {
         &data[2..]
@@ -181,6 +196,25 @@ mod tests {
         }
     }
 
+    mod parse_u256 {
+        use ethers::types::U256;
+        use pretty_assertions::assert_eq;
+
+        use super::super::parse_u256;
+
+        #[test]
+        fn should_parse_decimal_and_hex_numbers() {
+            assert_eq!(parse_u256("1420000000"), Ok(U256::from(1420000000)));
+            assert_eq!(parse_u256("0x54a37b00"), Ok(U256::from(1420000000)));
+        }
+
+        #[test]
+        fn should_fail_to_parse_invalid_number() {
+            assert!(parse_u256("0xzz").is_err());
+            assert!(parse_u256("").is_err());
+        }
+    }
+
     mod cumsum {
         use super::super::cumsum;
         use pretty_assertions::assert_eq;
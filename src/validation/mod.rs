@@ -0,0 +1,405 @@
+pub mod types;
+
+use std::{str::FromStr, sync::Arc};
+
+use ethers::{
+    abi::{decode, encode, ParamType, Token},
+    providers::Middleware,
+    types::{transaction::eip712::EIP712Domain, Address, Bytes, H256, U256},
+    utils::id,
+};
+
+use crate::{
+    constants::{MULTICALL3, ONE_INCH_ROUTER_V5},
+    limit_order::{
+        eip712::order_typed_data_builder::get_limit_order_v3_domain, types::LimitOrderV3Struct,
+        LimitOrder,
+    },
+    multicall::{
+        aggregate3, aggregate3_batched,
+        types::{Call3, CallResult},
+    },
+};
+
+use types::{OrderOnChainState, ValidationIssue, ValidationReport};
+
+pub const DEFAULT_BATCH_SIZE: usize = 300;
+
+// balanceOf, allowance and remaining
+const STATE_CALLS_COUNT: usize = 3;
+
+const BALANCE_OF: &str = "balanceOf(address)";
+const ALLOWANCE: &str = "allowance(address,address)";
+const REMAINING: &str = "remaining(bytes32)";
+const CHECK_PREDICATE: &str =
+    "checkPredicate((uint256,address,address,address,address,address,uint256,uint256,uint256,bytes))";
+
+// checks maker side of the order with a single multicall
+pub struct OrderValidator<M> {
+    client: Arc<M>,
+    domain: EIP712Domain,
+    router: Address,
+    multicall: Address,
+    batch_size: usize,
+}
+
+impl<M: Middleware> OrderValidator<M> {
+    pub fn new(client: Arc<M>, chain_id: u64) -> Self {
+        OrderValidator {
+            client,
+            domain: get_limit_order_v3_domain(&U256::from(chain_id)),
+            router: Address::from_str(ONE_INCH_ROUTER_V5).unwrap(),
+            multicall: Address::from_str(MULTICALL3).unwrap(),
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    pub fn with_multicall(mut self, multicall: Address) -> Self {
+        self.multicall = multicall;
+
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+
+        self
+    }
+
+    pub async fn validate(&self, order: &LimitOrderV3Struct) -> Result<ValidationReport, String> {
+        let order_hash = self.order_hash(order);
+        let has_predicate = !LimitOrder::decode(order).predicate().is_empty();
+
+        let mut calls = self.state_calls(order, &order_hash);
+
+        if has_predicate {
+            calls.push(call(self.router, CHECK_PREDICATE, &[order.to_token()]));
+        }
+
+        let results = aggregate3(self.client.as_ref(), &self.multicall, &calls).await?;
+
+        let state = decode_state(order, order_hash, &results[0..STATE_CALLS_COUNT])?;
+        let predicate =
+            has_predicate.then(|| decode_bool(&results[STATE_CALLS_COUNT]).unwrap_or(false));
+
+        let mut issues = vec![];
+
+        match state.maker_balance {
+            None => issues.push(ValidationIssue::BalanceUnavailable),
+            Some(available) if available < state.remaining_making_amount => {
+                issues.push(ValidationIssue::InsufficientBalance {
+                    required: state.remaining_making_amount,
+                    available,
+                })
+            }
+            _ => {}
+        }
+
+        match state.allowance {
+            None => issues.push(ValidationIssue::AllowanceUnavailable),
+            Some(available) if available < state.remaining_making_amount => {
+                issues.push(ValidationIssue::InsufficientAllowance {
+                    required: state.remaining_making_amount,
+                    available,
+                })
+            }
+            _ => {}
+        }
+
+        if state.remaining_making_amount.is_zero() {
+            issues.push(ValidationIssue::FilledOrCancelled);
+        }
+
+        if predicate == Some(false) {
+            issues.push(ValidationIssue::PredicateFailed);
+        }
+
+        Ok(ValidationReport {
+            order_hash: state.order_hash,
+            maker_balance: state.maker_balance,
+            allowance: state.allowance,
+            remaining_making_amount: state.remaining_making_amount,
+            predicate,
+            issues,
+        })
+    }
+
+    // state of many orders in aggregate3 requests of at most `batch_size` calls
+    pub async fn fetch_states(
+        &self,
+        orders: &[LimitOrderV3Struct],
+    ) -> Result<Vec<OrderOnChainState>, String> {
+        let order_hashes: Vec<H256> = orders.iter().map(|order| self.order_hash(order)).collect();
+
+        let calls: Vec<Call3> = orders
+            .iter()
+            .zip(&order_hashes)
+            .flat_map(|(order, order_hash)| self.state_calls(order, order_hash))
+            .collect();
+
+        let results = aggregate3_batched(
+            self.client.as_ref(),
+            &self.multicall,
+            &calls,
+            self.batch_size,
+        )
+        .await?;
+
+        orders
+            .iter()
+            .zip(order_hashes)
+            .zip(results.chunks(STATE_CALLS_COUNT))
+            .map(|((order, order_hash), results)| decode_state(order, order_hash, results))
+            .collect()
+    }
+
+    fn order_hash(&self, order: &LimitOrderV3Struct) -> H256 {
+        H256::from_str(&LimitOrder::get_order_hash_static(
+            order,
+            Some(&self.domain),
+        ))
+        .unwrap()
+    }
+
+    fn state_calls(&self, order: &LimitOrderV3Struct, order_hash: &H256) -> Vec<Call3> {
+        vec![
+            call(
+                order.maker_asset,
+                BALANCE_OF,
+                &[Token::Address(order.maker)],
+            ),
+            call(
+                order.maker_asset,
+                ALLOWANCE,
+                &[Token::Address(order.maker), Token::Address(self.router)],
+            ),
+            call(
+                self.router,
+                REMAINING,
+                &[Token::FixedBytes(order_hash.as_bytes().to_vec())],
+            ),
+        ]
+    }
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+fn decode_state(
+    order: &LimitOrderV3Struct,
+    order_hash: H256,
+    results: &[CallResult],
+) -> Result<OrderOnChainState, String> {
+    // remaining reverts with UnknownOrder until the order is touched
+    let remaining_making_amount = if results[2].success {
+        decode_uint(&results[2]).ok_or("Cannot decode remaining amount")?
+    } else {
+        order.making_amount
+    };
+
+    Ok(OrderOnChainState {
+        order_hash,
+        maker_balance: decode_uint(&results[0]),
+        allowance: decode_uint(&results[1]),
+        remaining_making_amount,
+    })
+}
+
+fn call(target: Address, signature: &str, args: &[Token]) -> Call3 {
+    Call3 {
+        target,
+        allow_failure: true,
+        call_data: Bytes::from([id(signature).to_vec(), encode(args)].concat()),
+    }
+}
+
+fn decode_uint(result: &CallResult) -> Option<U256> {
+    if !result.success {
+        return None;
+    }
+
+    decode(&[ParamType::Uint(256)], &result.return_data)
+        .ok()
+        .and_then(|mut tokens| tokens.remove(0).into_uint())
+}
+
+fn decode_bool(result: &CallResult) -> Option<bool> {
+    if !result.success {
+        return None;
+    }
+
+    decode(&[ParamType::Bool], &result.return_data)
+        .ok()
+        .and_then(|mut tokens| tokens.remove(0).into_bool())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use ethers::{
+        abi::{encode, Token},
+        providers::{MockProvider, Provider},
+        types::{Address, Bytes, U256},
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::limit_order::{
+        predicate::Predicate, types::LimitOrderV3Struct, InteractionsData, LimitOrder,
+        OrderInfoData,
+    };
+
+    use super::{
+        types::{OrderOnChainState, ValidationIssue},
+        OrderValidator,
+    };
+
+    fn order(predicate: Option<Predicate>) -> LimitOrderV3Struct {
+        LimitOrder::new(
+            &OrderInfoData {
+                maker_asset: Address::from_str("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")
+                    .unwrap(),
+                taker_asset: Address::from_str("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")
+                    .unwrap(),
+                making_amount: U256::from(1000),
+                taking_amount: U256::from(1420),
+                maker: Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+                salt: Some(U256::from(1)),
+                allowed_sender: None,
+                receiver: None,
+            },
+            Some(&InteractionsData {
+                predicate: predicate.map(|p| p.build()),
+                ..Default::default()
+            }),
+            &|| U256::from(1),
+        )
+        .build()
+    }
+
+    fn uint(value: u64) -> (bool, Vec<u8>) {
+        (true, encode(&[Token::Uint(U256::from(value))]))
+    }
+
+    fn response(results: &[(bool, Vec<u8>)]) -> Bytes {
+        encode(&[Token::Array(
+            results
+                .iter()
+                .map(|(success, data)| {
+                    Token::Tuple(vec![Token::Bool(*success), Token::Bytes(data.clone())])
+                })
+                .collect(),
+        )])
+        .into()
+    }
+
+    // mock answers requests in reverse order
+    fn mocked_batches(batches: &[&[(bool, Vec<u8>)]]) -> OrderValidator<Provider<MockProvider>> {
+        let (provider, mock) = Provider::mocked();
+
+        for results in batches.iter().rev() {
+            mock.push::<Bytes, _>(response(results)).unwrap();
+        }
+
+        OrderValidator::new(Arc::new(provider), 1)
+    }
+
+    fn mocked(results: &[(bool, Vec<u8>)]) -> OrderValidator<Provider<MockProvider>> {
+        mocked_batches(&[results])
+    }
+
+    #[tokio::test]
+    async fn should_validate_fillable_order() {
+        let validator = mocked(&[uint(5000), uint(u64::MAX), (false, vec![])]);
+
+        let report = validator.validate(&order(None)).await.unwrap();
+
+        assert!(report.is_valid());
+        assert_eq!(report.maker_balance, Some(U256::from(5000)));
+        assert_eq!(report.remaining_making_amount, U256::from(1000));
+        assert_eq!(report.predicate, None);
+    }
+
+    #[tokio::test]
+    async fn should_report_issues() {
+        let validator = mocked(&[
+            uint(100),
+            (false, vec![]),
+            uint(400),
+            (true, encode(&[Token::Bool(false)])),
+        ]);
+
+        let report = validator
+            .validate(&order(Some(Predicate::TimestampBelow(1673548200))))
+            .await
+            .unwrap();
+
+        assert_eq!(report.remaining_making_amount, U256::from(400));
+        assert_eq!(report.predicate, Some(false));
+        assert_eq!(
+            report.issues,
+            vec![
+                ValidationIssue::InsufficientBalance {
+                    required: U256::from(400),
+                    available: U256::from(100),
+                },
+                ValidationIssue::AllowanceUnavailable,
+                ValidationIssue::PredicateFailed,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_report_filled_order() {
+        let validator = mocked(&[uint(5000), uint(5000), uint(0)]);
+
+        let report = validator.validate(&order(None)).await.unwrap();
+
+        assert_eq!(report.issues, vec![ValidationIssue::FilledOrCancelled]);
+    }
+
+    #[tokio::test]
+    async fn should_fetch_states_in_batches() {
+        let validator = mocked_batches(&[
+            &[uint(5000), uint(5000)],
+            &[(false, vec![]), uint(10)],
+            &[(false, vec![]), uint(20)],
+        ])
+        .with_batch_size(2);
+
+        let orders = vec![order(None), order(Some(Predicate::TimestampBelow(100)))];
+        let states = validator.fetch_states(&orders).await.unwrap();
+
+        assert_eq!(
+            states,
+            vec![
+                OrderOnChainState {
+                    order_hash: validator.order_hash(&orders[0]),
+                    maker_balance: Some(U256::from(5000)),
+                    allowance: Some(U256::from(5000)),
+                    remaining_making_amount: U256::from(1000),
+                },
+                OrderOnChainState {
+                    order_hash: validator.order_hash(&orders[1]),
+                    maker_balance: Some(U256::from(10)),
+                    allowance: None,
+                    remaining_making_amount: U256::from(20),
+                },
+            ]
+        );
+        assert_ne!(states[0].order_hash, states[1].order_hash);
+    }
+
+    #[tokio::test]
+    async fn should_refuse_mismatched_response() {
+        let validator = mocked(&[uint(5000)]);
+
+        assert_eq!(
+            validator.validate(&order(None)).await,
+            Err("Expected 3 multicall results, got 1".to_string())
+        );
+    }
+}
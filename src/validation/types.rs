@@ -0,0 +1,31 @@
+use ethers::types::{H256, U256};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    BalanceUnavailable,
+    AllowanceUnavailable,
+    InsufficientBalance { required: U256, available: U256 },
+    InsufficientAllowance { required: U256, available: U256 },
+    FilledOrCancelled,
+    // predicate returned false or reverted
+    PredicateFailed,
+}
+
+// on-chain state of a single order fetched by multicall
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderOnChainState {
+    pub order_hash: H256,
+    pub maker_balance: Option<U256>,
+    pub allowance: Option<U256>,
+    pub remaining_making_amount: U256,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationReport {
+    pub order_hash: H256,
+    pub maker_balance: Option<U256>,
+    pub allowance: Option<U256>,
+    pub remaining_making_amount: U256,
+    pub predicate: Option<bool>, // None when order has no predicate
+    pub issues: Vec<ValidationIssue>,
+}